@@ -0,0 +1,125 @@
+use mlrs::random::Rng;
+use mlrs::tensor::Tensor;
+
+// Largest absolute difference between two tensors
+fn max_error(a: &Tensor, b: &Tensor) -> f64 {
+    (a - b).abs().max_all().item()
+}
+
+// Reports an error that rounding alone should keep tiny, and fails loudly otherwise
+fn check(what: &str, error: f64) {
+    println!("{}: {:e}", what, error);
+    assert!(error < 1e-10, "{} is too large", what);
+}
+
+// Distance of the columns of `q` from an orthonormal set
+fn orthogonality_error(q: &Tensor) -> f64 {
+    max_error(&q.t().matmul(q), &Tensor::eye(q.shape()[1]))
+}
+
+// Checks A = U diag(S) Vt, the orthonormality of U and V and the order of S
+fn check_svd(name: &str, a: &Tensor, full_matrices: bool) {
+    let (m, n) = (a.shape()[0], a.shape()[1]);
+    let k = m.min(n);
+    let (u, s, vt) = a.svd(full_matrices);
+    let (u_cols, v_rows) = if full_matrices { (m, n) } else { (k, k) };
+    assert_eq!(u.shape(), [m, u_cols]);
+    assert_eq!(vt.shape(), [v_rows, n]);
+    let s_values = s.to_vec();
+    assert!(s_values.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert!(s_values.iter().all(|&x| x >= 0.0));
+
+    // Only the first k columns of U and rows of Vt meet a singular value
+    let reconstructed = u
+        .narrow(1, 0, k)
+        .matmul(&s.diag())
+        .matmul(&vt.narrow(0, 0, k));
+    check(
+        &format!("{} svd reconstruction error", name),
+        max_error(&reconstructed, a),
+    );
+    check(
+        &format!("{} svd U orthogonality error", name),
+        orthogonality_error(&u),
+    );
+    check(
+        &format!("{} svd V orthogonality error", name),
+        orthogonality_error(&vt.t()),
+    );
+}
+
+fn main() {
+    let mut rng = Rng::new(42);
+
+    // The SVD is exact up to rounding for tall, wide and rank-deficient matrices, in
+    // the thin and the full form
+    let tall = Tensor::randn(vec![6, 4], &mut rng);
+    let wide = Tensor::randn(vec![3, 5], &mut rng);
+    let deficient =
+        Tensor::randn(vec![6, 2], &mut rng).matmul(&Tensor::randn(vec![2, 5], &mut rng));
+    for full_matrices in [false, true] {
+        let form = if full_matrices { "full" } else { "thin" };
+        check_svd(&format!("{} tall", form), &tall, full_matrices);
+        check_svd(&format!("{} wide", form), &wide, full_matrices);
+        check_svd(
+            &format!("{} rank-deficient", form),
+            &deficient,
+            full_matrices,
+        );
+    }
+    println!(
+        "singular values of the rank 2 matrix: {:.4}",
+        deficient.svd(false).1
+    );
+
+    // Low-rank compression of a layer weight: keep the `rank` largest singular values
+    let weight = Tensor::randn(vec![32, 4], &mut rng).matmul(&Tensor::randn(vec![4, 16], &mut rng))
+        + Tensor::randn(vec![32, 16], &mut rng) * 0.01;
+    let (u, s, vt) = weight.svd(false);
+    for rank in [2, 4, 8] {
+        let compressed = u
+            .narrow(1, 0, rank)
+            .matmul(&s.narrow(0, 0, rank).diag())
+            .matmul(&vt.narrow(0, 0, rank));
+        println!(
+            "rank {} approximation error: {:.6} ({} instead of {} parameters)",
+            rank,
+            max_error(&compressed, &weight),
+            rank * (32 + 16 + 1),
+            32 * 16
+        );
+    }
+
+    // The pseudo-inverse of a rank-deficient matrix satisfies the Penrose conditions
+    // A A+ A = A and A+ A A+ = A+, where lstsq would report it as singular
+    let deficient =
+        Tensor::randn(vec![5, 2], &mut rng).matmul(&Tensor::randn(vec![2, 4], &mut rng));
+    let inverse = deficient.pinv(None);
+    let penrose_a = max_error(&deficient.matmul(&inverse).matmul(&deficient), &deficient);
+    let penrose_pinv = max_error(&inverse.matmul(&deficient).matmul(&inverse), &inverse);
+    check("pinv A A+ A error", penrose_a);
+    check("pinv A+ A A+ error", penrose_pinv);
+
+    // PCA of correlated samples from the eigendecomposition of their covariance
+    let mixing = Tensor::from_data(vec![2, 2], vec![2.0, 0.0, 1.5, 0.5]);
+    let samples = Tensor::randn(vec![500, 2], &mut rng).matmul(&mixing.t());
+    let centered = &samples - &samples.mean(0, true);
+    let covariance = centered.t().matmul(&centered) / 499.0;
+    let (variances, components) = covariance.eigh();
+    println!("principal variances: {:.4}", variances);
+    println!("principal components:\n{:.4}", components);
+    let rebuilt = components.matmul(&variances.diag()).matmul(&components.t());
+    check(
+        "eigh reconstruction error",
+        max_error(&rebuilt, &covariance),
+    );
+    check("eigh orthogonality error", orthogonality_error(&components));
+
+    // Whitening rotates onto the components and scales them to unit variance
+    let whitened = centered.matmul(&components) / variances.sqrt();
+    let white_covariance = whitened.t().matmul(&whitened) / 499.0;
+    check(
+        "whitened covariance error",
+        max_error(&white_covariance, &Tensor::eye(2)),
+    );
+}
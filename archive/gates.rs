@@ -1,88 +1,78 @@
-use rand::Rng;
+use mlrs::init::{Init, Nonlinearity};
+use mlrs::loss::{self, Reduction};
+use mlrs::nn::{Module, NeuralNetwork};
+use mlrs::optim::{Optimizer, Sgd};
+use mlrs::random::Rng;
+use mlrs::tensor::Tensor;
 
-fn sigmoid(x: &f64) -> f64 {
-    1.0 / (1.0 + (-x).exp())
+fn mse(net: &NeuralNetwork, x: &Tensor, y: &Tensor) -> Tensor {
+    loss::mse(&net.forward(x), y, Reduction::Mean)
 }
 
-// TODO: implement random from scratch
-fn mse(data: &Vec<(f64, f64, f64)>, w1: &f64, w2: &f64, b: &f64) -> f64 {
-    // mse
-    data.iter()
-        .map(|(x1, x2, y)| {
-            let y_pred = sigmoid(&(w1 * x1 + w2 * x2 + b));
-            let err = y_pred - y;
-            err * err
-        })
-        .sum::<f64>()
-        / data.len() as f64
-}
-
-fn train(data: &Vec<(f64, f64, f64)>) {
-    let mut rng = rand::thread_rng();
-    // y = x * w
-    let mut w1: f64 = rng.gen_range(0.0..1.0);
-    let mut w2: f64 = rng.gen_range(0.0..1.0);
-    let mut b: f64 = rng.gen_range(0.0..5.0);
-    let eps = 1e-1;
-    let lr = 1e-1;
-    let mut cost = mse(data, &w1, &w2, &b);
-    // finite difference to calculate cost distance (usually done with derrivative)
-    println!("initial cost: {};", mse(data, &w1, &w2, &b));
+fn train(x: &Tensor, y: &Tensor, rng: &mut Rng) {
+    // y = sigmoid(w * x + b)
+    let weight = Init::XavierUniform {
+        gain: Nonlinearity::Sigmoid.gain(),
+    };
+    let mut net = NeuralNetwork::new(
+        vec![weight.tensor(vec![1, 2], rng)],
+        vec![Init::zeros().tensor(vec![1, 1], rng)],
+    );
+    for param in net.parameters_mut() {
+        param.set_requires_grad(true);
+    }
+    let mut optimizer = Sgd::new(1e-1);
+    println!("initial cost: {};", mse(&net, x, y).item());
     for _ in 0..10000 {
-        cost = mse(data, &w1, &w2, &b);
-        let dw1 = (mse(data, &(w1 + eps), &w2, &b) - cost) / eps;
-        let dw2 = (mse(data, &w1, &(w2 + eps), &b) - cost) / eps;
-        let db = (mse(data, &w1, &w2, &(b + eps)) - cost) / eps;
-        w1 -= dw1 * lr;
-        w2 -= dw2 * lr;
-        b -= db * lr;
+        // backpropagate the cost to every parameter
+        mse(&net, x, y).backward();
+        optimizer.step(&mut net);
+        net.zero_grad();
     }
     println!(
-        "cost: {:.2}; w1: {:.2}; w2: {:.2}; b: {:.2};",
-        cost, w1, w2, b
+        "cost: {:.2}; w: {:.2?}; b: {:.2?};",
+        mse(&net, x, y).item(),
+        net.weights[0].to_vec(),
+        net.biases[0].to_vec()
     );
 
-    data.iter().for_each(|(x1, x2, _y)| {
-        println!("{} | {} = {}", x1, x2, sigmoid(&(w1 * x1 + w2 * x2 + b)))
-    })
+    for (i, pred) in net.forward(x).iter().enumerate() {
+        println!("{} | {} = {}", x[[0, i]], x[[1, i]], pred);
+    }
 }
 
 fn main() {
+    // Every column is one sample: [x1; x2]
+    let x = Tensor::from_data(vec![2, 4], vec![1.0, 1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0]);
+    let mut rng = Rng::new(42);
+
     println!("Single neuron");
     println!("OR gate");
-    let mut data = vec![
-        (1.0, 1.0, 1.0),
-        (1.0, 0.0, 1.0),
-        (0.0, 1.0, 1.0),
-        (0.0, 0.0, 0.0),
-    ];
-    train(&data);
+    train(
+        &x,
+        &Tensor::from_data(vec![1, 4], vec![1.0, 1.0, 1.0, 0.0]),
+        &mut rng,
+    );
 
     println!("AND gate");
-    data = vec![
-        (1.0, 1.0, 1.0),
-        (1.0, 0.0, 0.0),
-        (0.0, 1.0, 0.0),
-        (0.0, 0.0, 0.0),
-    ];
-    train(&data);
+    train(
+        &x,
+        &Tensor::from_data(vec![1, 4], vec![1.0, 0.0, 0.0, 0.0]),
+        &mut rng,
+    );
 
     println!("NAND gate");
-    data = vec![
-        (1.0, 1.0, 0.0),
-        (1.0, 0.0, 1.0),
-        (0.0, 1.0, 1.0),
-        (0.0, 0.0, 1.0),
-    ];
-    train(&data);
+    train(
+        &x,
+        &Tensor::from_data(vec![1, 4], vec![0.0, 1.0, 1.0, 1.0]),
+        &mut rng,
+    );
 
     // (x|y) & ~(x&y)
     println!("XOR gate");
-    data = vec![
-        (1.0, 1.0, 0.0),
-        (1.0, 0.0, 1.0),
-        (0.0, 1.0, 1.0),
-        (0.0, 0.0, 0.0),
-    ];
-    train(&data);
+    train(
+        &x,
+        &Tensor::from_data(vec![1, 4], vec![0.0, 1.0, 1.0, 0.0]),
+        &mut rng,
+    );
 }
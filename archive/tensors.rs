@@ -1,248 +1,120 @@
-struct _Tensor {
-    shape: Vec<usize>,
-    data: Vec<f64>,
-}
-
-impl _Tensor {
-    // Constructor for a tensor with a given shape, initialized to zeros
-    fn _new(shape: Vec<usize>) -> _Tensor {
-        assert!(
-            !shape.contains(&0),
-            "Shape dimensions must be positive and cannot include zero."
-        );
-        let total_size: usize = shape.iter().product();
-        _Tensor {
-            shape,
-            data: vec![0.0; total_size],
-        }
-    }
-
-    // Constructor for a tensor with a given shape and initial data
-    // This requires that the length of data matches the product of the shape's dimensions
-    fn _from_data(mut shape: Vec<usize>, data: Vec<f64>) -> _Tensor {
-        let zero_count = shape.iter().filter(|&&x| x == 0).count();
-        assert!(
-            zero_count <= 1,
-            "Only one dimension can be inferred (zero)."
-        );
-
-        if zero_count == 1 {
-            let known_product: usize = shape.iter().filter(|&&x| x != 0).product();
-            let inferred_index = shape.iter().position(|&x| x == 0).unwrap();
-            shape[inferred_index] = data.len() / known_product;
-        }
-        assert_eq!(
-            shape.iter().product::<usize>(),
-            data.len(),
-            "The product of the shape must match the length of the data."
-        );
-        _Tensor { shape, data }
-    }
-
-    fn matmul(&self, other: &_Tensor) -> _Tensor {
-        assert_eq!(self.shape.len(), 2, "First tensor is not a 2D matrix.");
-        assert_eq!(other.shape.len(), 2, "Second tensor is not a 2D matrix.");
-        assert_eq!(
-            self.shape[1], other.shape[0],
-            "Dimensions do not match for matrix multiplication."
-        );
-
-        let result_rows = self.shape[0];
-        let result_cols = other.shape[1];
-        let mut result_data = vec![0.0; result_rows * result_cols];
-
-        for i in 0..result_rows {
-            for j in 0..result_cols {
-                let mut sum = 0.0;
-                for k in 0..self.shape[1] {
-                    sum += self.data[i * self.shape[1] + k] * other.data[k * other.shape[1] + j];
-                }
-                result_data[i * result_cols + j] = sum;
-            }
-        }
-
-        _Tensor {
-            shape: vec![result_rows, result_cols],
-            data: result_data,
-        }
-    }
-
-    // Matrix addition
-    fn matadd(&self, other: &_Tensor) -> _Tensor {
-        assert_eq!(
-            self.shape, other.shape,
-            "Shapes do not match for matrix addition."
-        );
-
-        let result_data: Vec<f64> = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(&x, &y)| x + y)
-            .collect();
-
-        _Tensor {
-            shape: self.shape.clone(),
-            data: result_data,
-        }
-    }
-    // Matrix addition
-    fn matsub(&self, other: &_Tensor) -> _Tensor {
-        assert_eq!(
-            self.shape, other.shape,
-            "Shapes do not match for matrix addition."
-        );
-
-        let result_data: Vec<f64> = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(&x, &y)| x - y)
-            .collect();
-
-        _Tensor {
-            shape: self.shape.clone(),
-            data: result_data,
-        }
-    }
-}
-
-impl std::ops::Mul for _Tensor {
-    type Output = _Tensor;
-
-    fn mul(self, other: _Tensor) -> _Tensor {
-        let len1 = self.shape.len();
-        let len2 = other.shape.len();
-        let max_len = std::cmp::max(len1, len2);
-        let mut result_shape = Vec::with_capacity(max_len);
-
-        let mut stride1 = 1;
-        let mut stride2 = 1;
-        let mut expanded_data1 = self.data;
-        let mut expanded_data2 = other.data;
-
-        for i in 0..max_len {
-            let dim1 = if i < len1 {
-                self.shape[len1 - 1 - i]
-            } else {
-                1
-            };
-            let dim2 = if i < len2 {
-                other.shape[len2 - 1 - i]
-            } else {
-                1
-            };
-
-            assert!(
-                dim1 == dim2 || dim1 == 1 || dim2 == 1,
-                "Shapes could not be broadcast together."
-            );
-
-            result_shape.insert(0, std::cmp::max(dim1, dim2));
-
-            if dim1 == 1 && dim2 != 1 {
-                expanded_data1 = expanded_data1
-                    .chunks(stride1)
-                    .flat_map(|chunk| chunk.iter().cycle().take(stride1 * dim2))
-                    .copied()
-                    .collect();
-            }
-
-            if dim2 == 1 && dim1 != 1 {
-                expanded_data2 = expanded_data2
-                    .chunks(stride2)
-                    .flat_map(|chunk| chunk.iter().cycle().take(stride2 * dim1))
-                    .copied()
-                    .collect();
-            }
-
-            stride1 *= dim1;
-            stride2 *= dim2;
-        }
+use mlrs::s;
+use mlrs::tensor::Tensor;
 
-        let result_data = expanded_data1
-            .into_iter()
-            .zip(expanded_data2)
-            .map(|(x, y)| x * y)
-            .collect();
-
-        _Tensor {
-            shape: result_shape,
-            data: result_data,
-        }
-    }
-}
-
-impl Clone for _Tensor {
-    fn clone(&self) -> Self {
-        _Tensor {
-            shape: self.shape.clone(),
-            data: self.data.clone(),
-        }
-    }
-}
-
-fn sigmoid(x: &f64) -> f64 {
-    1.0 / (1.0 + (-x).exp())
-}
-
-struct NeuralNetwork {
-    weights: Vec<_Tensor>,
-    biases: Vec<_Tensor>,
-}
-
-fn forward(net: &NeuralNetwork, input: &_Tensor) -> _Tensor {
-    let mut current_output = input.clone();
-
-    for (weight, bias) in net.weights.iter().zip(net.biases.iter()) {
-        // Apply linear transformation: weight * input + bias
-        current_output = weight.matmul(&current_output).matadd(bias);
-        // Apply activation function (e.g., sigmoid)
-        for elem in &mut current_output.data {
-            *elem = sigmoid(elem);
-        }
-    }
-    current_output
-}
-
-fn backprop(net: &NeuralNetwork, in: &_Tensor, out: &_Tensor) {
-    
-}
-
-// Example Usage
 fn main() {
-    // Create two tensors of shape [2, 2] and initialize them with some data
-    let tensor1 = _Tensor::_from_data(vec![2, 2], vec![1.0, 2.0, 3.0, 4.0]);
-    let tensor2 = _Tensor::_from_data(vec![2, 2], vec![5.0, 6.0, 7.0, 8.0]);
-
-    // Multiply the two tensors
-    let result = tensor1 * tensor2;
-
-    // Print the result
-    println!("Resulting shape: {:?}", result.shape);
-    println!("Resulting data: {:?}", result.data);
-
-    // Create a tensor of shape [3] and another tensor of shape [3, 1]
-    let tensor1 = _Tensor::_from_data(vec![3], vec![1.0, 2.0, 3.0]);
-    let tensor2 = _Tensor::_from_data(vec![3, 1], vec![4.0, 5.0, 6.0]);
-
-    // Multiply the two tensors (broadcasting will occur)
-    let result = tensor1 * tensor2;
-
-    // Print the result
-    println!("Resulting shape: {:?}", result.shape);
-    println!("Resulting data: {:?}", result.data);
-
-    // Define a 2x3 tensor (matrix)
-    let tensor1 = _Tensor::_from_data(vec![2, 3], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
-
-    // Define a 3x2 tensor (matrix)
-    let tensor2 = _Tensor::_from_data(vec![3, 2], vec![7.0, 8.0, 9.0, 10.0, 11.0, 12.0]);
-
-    // Perform matrix multiplication
-    let result = tensor1.matmul(&tensor2);
-
-    // Print the result
-    println!("Resulting shape: {:?}", result.shape);
-    println!("Resulting data: {:?}", result.data);
+    let a = Tensor::from_data(vec![2, 3], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = Tensor::from_data(vec![2, 3], vec![6.0, 5.0, 4.0, 3.0, 2.0, 1.0]);
+    // Row vector broadcast against every row of `a`
+    let row = Tensor::from_data(vec![3], vec![10.0, 20.0, 30.0]);
+    // Column vector broadcast against every column of `a`
+    let col = Tensor::from_data(vec![2, 1], vec![2.0, 4.0]);
+
+    let sum = a.matadd(&b);
+    println!("a + b: {:?} {:?}", sum.shape(), sum.to_vec());
+    let diff = a.matsub(&b);
+    println!("a - b: {:?} {:?}", diff.shape(), diff.to_vec());
+
+    let result = a.clone() + row.clone();
+    println!("a + row: {:?} {:?}", result.shape(), result.to_vec());
+    let result = a.clone() - row;
+    println!("a - row: {:?} {:?}", result.shape(), result.to_vec());
+    let result = a.clone() * col.clone();
+    println!("a * col: {:?} {:?}", result.shape(), result.to_vec());
+    let result = a / col;
+    println!("a / col: {:?} {:?}", result.shape(), result.to_vec());
+
+    // [3] * [3, 1] broadcasts to [3, 3]
+    let x = Tensor::from_data(vec![3], vec![1.0, 2.0, 3.0]);
+    let y = Tensor::from_data(vec![3], vec![4.0, 5.0, 6.0]).reshape(&[-1, 1]);
+    let outer = x * y;
+    println!("outer: {:?} {:?}", outer.shape(), outer.to_vec());
+
+    // Batch dimensions broadcast: every [2, 3] matrix of the batch times the same [3, 2]
+    let batch = Tensor::from_data(vec![2, 2, 3], (1..=12).map(f64::from).collect());
+    let weight = Tensor::from_data(vec![3, 2], vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+    let result = batch.matmul(&weight);
+    println!("batch @ weight: {:?} {:?}", result.shape(), result.to_vec());
+    // Vectors are promoted, a vector times a vector is their dot product
+    let v = Tensor::from_data(vec![3], vec![1.0, 2.0, 3.0]);
+    println!(
+        "v @ v: {:?} {:?}",
+        v.matmul(&v).shape(),
+        v.matmul(&v).to_vec()
+    );
+
+    // Shape ops are views over the same storage
+    let images = Tensor::from_data(vec![2, 3, 4], (1..=24).map(f64::from).collect());
+    let channels_last = images.permute(&[0, 2, 1]);
+    println!(
+        "permute: {:?} {:?}",
+        channels_last.shape(),
+        channels_last.strides()
+    );
+    let rows = images.reshape(&[-1, 4]);
+    println!("reshape [-1, 4]: {:?}", rows.shape());
+    let flat = images.flatten(1, 2).unsqueeze(1);
+    println!("flatten + unsqueeze: {:?}", flat.shape());
+    let expanded = flat.expand(&[-1, 5, -1]);
+    println!("expand: {:?} {:?}", expanded.shape(), expanded.strides());
+
+    // Indexing reads single elements, slices are views with NumPy-like selectors
+    println!("images[1, 2, 3]: {}", images[[1, 2, 3]]);
+    let corners = images.slice(s![.., ..;2, -1]);
+    println!(
+        "images[:, ::2, -1]: {:?} {:?}",
+        corners.shape(),
+        corners.to_vec()
+    );
+    let mut masked = images.clone();
+    masked.slice_assign(s![0, 1.., ..2], &Tensor::from_data(vec![1], vec![0.0]));
+    println!("masked[0]: {:?}", masked.slice(s![0]).to_vec());
+
+    // Samples are stacked into a batch and the batch is split back into halves
+    let samples: Vec<Tensor> = (0..4)
+        .map(|i| Tensor::from_data(vec![2], vec![i as f64, -(i as f64)]))
+        .collect();
+    let batch = Tensor::stack(&samples.iter().collect::<Vec<_>>(), 0);
+    let halves = batch.chunk(2, 0);
+    println!(
+        "stack: {:?}, chunk: {:?}",
+        batch.shape(),
+        halves[1].to_vec()
+    );
+    let joined = Tensor::cat(&[&halves[1], &halves[0]], 0);
+    println!("cat: {:?}", joined.to_vec());
+    println!("tile: {:?}", samples[1].tile(&[2, 2]).to_vec());
+
+    // Reductions with keepdim broadcast back against the input, e.g. to standardize rows
+    let scores = Tensor::from_data(vec![2, 3], vec![1.0, 4.0, 7.0, 2.0, 2.0, 8.0]);
+    let standardized = (scores.clone() - scores.mean(1, true)) / scores.std(1, 0, true);
+    println!("standardized rows: {:?}", standardized.to_vec());
+    println!(
+        "column max: {:?}, argmax: {:?}",
+        scores.max(0, false).to_vec(),
+        scores.argmax(0, false).to_vec()
+    );
+
+    // Empty tensors flow through ops, full reductions give 0-D tensors
+    let empty = Tensor::<f64>::zeros(vec![0, 3]);
+    let projected = empty.matmul(&Tensor::ones(vec![3, 2])) + Tensor::ones(vec![2]);
+    println!(
+        "empty batch: {:?}, column sums: {:?}",
+        projected.shape(),
+        projected.sum(0, false).to_vec()
+    );
+    let total = scores.sum_all();
+    println!("sum: {:?} {}", total.shape(), total.item());
+
+    // Integer tensors use the same ops, casts convert between element types
+    let counts = Tensor::<i32>::from_data(vec![2, 2], vec![1, 0, 2, 3]);
+    let squared = counts.matmul(&counts);
+    println!(
+        "counts @ counts: {:?} {:?}",
+        squared.shape(),
+        squared.to_vec()
+    );
+    let mask: Tensor<bool> = counts.cast();
+    println!("non-zero: {:?}", mask.to_vec());
+    let halves = counts.cast::<f32>() / Tensor::from_data(vec![1], vec![2.0]);
+    println!("counts / 2: {:?}", halves.to_vec());
 }
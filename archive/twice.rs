@@ -1,34 +1,39 @@
-use rand::Rng;
+use mlrs::autograd::no_grad;
+use mlrs::loss::{self, Reduction};
+use mlrs::random::Rng;
+use mlrs::tensor::Tensor;
 
-// TODO: implement random from scratch
-fn cost(data: &Vec<(f64, f64)>, w: &f64, b: &f64) -> f64 {
-    // mse
-    data.iter()
-        .map(|(x, y)| {
-            let y_pred = w * x + b;
-            let err = y_pred - y;
-            err * err
-        })
-        .sum::<f64>()
-        / data.len() as f64
+fn cost(x: &Tensor, y: &Tensor, w: &Tensor, b: &Tensor) -> Tensor {
+    let y_pred = x * w + b;
+    loss::mse(&y_pred, y, Reduction::Mean)
 }
 
 fn main() {
-    let data = vec![(0.0, 0.0), (1.0, 2.0), (2.0, 4.0), (3.0, 6.0), (4.0, 8.0)];
-    let mut rng = rand::thread_rng();
+    let x = Tensor::from_data(vec![5, 1], vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    let y = Tensor::from_data(vec![5, 1], vec![0.0, 2.0, 4.0, 6.0, 8.0]);
+    let mut rng = Rng::new(42);
     // y = x * w
-    let mut w: f64 = rng.gen_range(0.0..10.0);
-    let mut b: f64 = rng.gen_range(0.0..5.0);
-    let eps = 1e-3;
+    let mut w = Tensor::uniform(vec![1], 0.0, 10.0, &mut rng);
+    let mut b = Tensor::uniform(vec![1], 0.0, 5.0, &mut rng);
+    w.set_requires_grad(true);
+    b.set_requires_grad(true);
     let lr = 1e-3;
-    // finite difference to calculate cost distance (usually done with derrivative)
-    println!("cost: {};", cost(&data, &w, &b));
+    println!("cost: {};", cost(&x, &y, &w, &b).item());
     for _ in 0..500 {
-        let c = cost(&data, &w, &b);
-        let dw = (cost(&data, &(w + eps), &b) - c) / eps;
-        let db = (cost(&data, &w, &(b + eps)) - c) / eps;
-        w -= dw * lr;
-        b -= db * lr;
-        println!("cost: {}; w: {}; b: {};", c, w, b);
+        let c = cost(&x, &y, &w, &b);
+        // backpropagate the cost to w and b
+        c.backward();
+        no_grad(|| {
+            w -= w.grad().unwrap() * lr;
+            b -= b.grad().unwrap() * lr;
+        });
+        w.zero_grad();
+        b.zero_grad();
+        println!("cost: {}; w: {}; b: {};", c.item(), w.item(), b.item());
     }
+
+    // Closed form: least squares fit of [x, 1] * [w, b] = y
+    let design = Tensor::cat(&[&x, &x.ones_like()], 1);
+    let fit = design.lstsq(&y);
+    println!("lstsq: w: {}; b: {};", fit[[0, 0]], fit[[1, 0]]);
 }
@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+use mlrs::gemm::{self, MatRef};
+use mlrs::random::Rng;
+
+// Deterministic inputs in [-1, 1) so runs are comparable
+fn matrix(n: usize, seed: u64) -> Vec<f64> {
+    let mut rng = Rng::new(seed);
+    (0..n * n).map(|_| rng.uniform(-1.0, 1.0)).collect()
+}
+
+fn row_major(data: &[f64], n: usize) -> MatRef<'_, f64> {
+    MatRef {
+        data,
+        offset: 0,
+        row_stride: n,
+        col_stride: 1,
+    }
+}
+
+// Best of `runs` timings, the first run also warms up the caches
+fn time<F: FnMut()>(runs: usize, mut f: F) -> Duration {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    // `cargo test` runs benchmarks without `--bench` to check that they work,
+    // keep that fast by only timing under `cargo bench`
+    let sizes: &[usize] = if std::env::args().any(|arg| arg == "--bench") {
+        &[512, 1024]
+    } else {
+        &[64]
+    };
+
+    for &n in sizes {
+        let (a, b) = (matrix(n, 1), matrix(n, 2));
+        let (a, b) = (row_major(&a, n), row_major(&b, n));
+        let mut reference = vec![0.0; n * n];
+        let mut result = vec![0.0; n * n];
+
+        let naive = time(1, || gemm::gemm_naive(a, b, &mut reference, n, n, n));
+
+        gemm::set_num_threads(1);
+        let blocked = time(3, || gemm::gemm(a, b, &mut result, n, n, n));
+        gemm::set_num_threads(0);
+        let threaded = time(3, || gemm::gemm(a, b, &mut result, n, n, n));
+
+        let max_error = reference
+            .iter()
+            .zip(&result)
+            .map(|(x, y)| (x - y).abs())
+            .fold(0.0, f64::max);
+        let speedup = |d: Duration| naive.as_secs_f64() / d.as_secs_f64();
+
+        println!("matmul {}x{} (f64)", n, n);
+        println!("  naive:    {:>10.2?}", naive);
+        println!("  blocked:  {:>10.2?}  {:>6.1}x", blocked, speedup(blocked));
+        println!(
+            "  threaded: {:>10.2?}  {:>6.1}x  ({} threads)",
+            threaded,
+            speedup(threaded),
+            gemm::num_threads()
+        );
+        println!("  max abs difference from naive: {:e}", max_error);
+        assert!(
+            max_error < 1e-9,
+            "Blocked kernel disagrees with the reference."
+        );
+    }
+}
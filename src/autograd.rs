@@ -0,0 +1,182 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::dtype::{Element, Float};
+use crate::tensor::Tensor;
+
+// Computes the gradient of every input of an operation from the gradient of its output
+type BackwardFn<T> = Box<dyn Fn(&Tensor<T>) -> Vec<Tensor<T>>>;
+
+// A vertex of the computation graph. Leaves (tensors marked with `set_requires_grad`)
+// have no parents and no backward function, their `grad` accumulates across calls
+// to `backward` until it is cleared with `zero_grad`.
+pub(crate) struct Node<T> {
+    parents: Vec<Option<Rc<Node<T>>>>,
+    backward: Option<BackwardFn<T>>,
+    grad: RefCell<Option<Tensor<T>>>,
+    // Shape of the tensor the node belongs to, gradients are reduced to it
+    shape: Vec<usize>,
+}
+
+thread_local! {
+    static GRAD_ENABLED: Cell<bool> = const { Cell::new(true) };
+}
+
+// Runs `f` without recording any operations on the graph, e.g. for parameter updates
+pub fn no_grad<R>(f: impl FnOnce() -> R) -> R {
+    let previous = GRAD_ENABLED.with(|enabled| enabled.replace(false));
+    let result = f();
+    GRAD_ENABLED.with(|enabled| enabled.set(previous));
+    result
+}
+
+pub fn is_grad_enabled() -> bool {
+    GRAD_ENABLED.with(|enabled| enabled.get())
+}
+
+// Attaches a graph node to `output` if any of `inputs` is tracked. `backward` receives
+// the gradient of `output` and must return one gradient per input, in the same order
+// as the inputs. A gradient may also have a shape the input broadcasts to, it is then
+// summed back down to the shape of the input.
+pub(crate) fn record<T, F>(mut output: Tensor<T>, inputs: &[&Tensor<T>], backward: F) -> Tensor<T>
+where
+    T: Element,
+    F: Fn(&Tensor<T>) -> Vec<Tensor<T>> + 'static,
+{
+    if !is_grad_enabled() || inputs.iter().all(|t| t.node.is_none()) {
+        return output;
+    }
+    output.node = Some(Rc::new(Node {
+        parents: inputs.iter().map(|t| t.node.clone()).collect(),
+        backward: Some(Box::new(backward)),
+        grad: RefCell::new(None),
+        shape: output.shape().to_vec(),
+    }));
+    output
+}
+
+// Nodes reachable from `root`, ordered so that every node comes after all of its parents
+fn topological_order<T>(root: &Rc<Node<T>>) -> Vec<Rc<Node<T>>> {
+    let mut order = Vec::new();
+    let mut visited = HashSet::new();
+    // (node, whether its parents have already been pushed)
+    let mut stack = vec![(root.clone(), false)];
+
+    while let Some((node, expanded)) = stack.pop() {
+        if expanded {
+            order.push(node);
+            continue;
+        }
+        if !visited.insert(Rc::as_ptr(&node)) {
+            continue;
+        }
+        stack.push((node.clone(), true));
+        for parent in node.parents.iter().flatten() {
+            if !visited.contains(&Rc::as_ptr(parent)) {
+                stack.push((parent.clone(), false));
+            }
+        }
+    }
+    order
+}
+
+fn accumulate<T: Float>(node: &Node<T>, grad: Tensor<T>) {
+    let grad = if grad.shape() == node.shape {
+        grad
+    } else {
+        grad.sum_to_shape(&node.shape)
+    };
+    let mut slot = node.grad.borrow_mut();
+    *slot = Some(match slot.take() {
+        Some(current) => current.elemwise_with_broadcast(&grad, |x, y| x + y),
+        None => grad,
+    });
+}
+
+impl<T: Element> Tensor<T> {
+    // ========================================================================
+    // Automatic differentiation
+
+    pub fn requires_grad(&self) -> bool {
+        self.node.is_some()
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.node
+            .as_ref()
+            .is_none_or(|node| node.backward.is_none())
+    }
+
+    // Gradient accumulated by `backward`, only kept for leaf tensors
+    pub fn grad(&self) -> Option<Tensor<T>> {
+        self.node
+            .as_ref()
+            .and_then(|node| node.grad.borrow().clone())
+    }
+
+    pub fn zero_grad(&self) {
+        if let Some(node) = &self.node {
+            *node.grad.borrow_mut() = None;
+        }
+    }
+
+    // Copy of the tensor that is not connected to the graph
+    pub fn detach(&self) -> Tensor<T> {
+        self.as_strided(
+            self.shape().to_vec(),
+            self.strides().to_vec(),
+            self.offset(),
+        )
+    }
+}
+
+impl<T: Float> Tensor<T> {
+    // Marks a tensor as a leaf of the graph so operations on it are recorded
+    pub fn set_requires_grad(&mut self, requires_grad: bool) {
+        self.node = if requires_grad {
+            Some(Rc::new(Node {
+                parents: Vec::new(),
+                backward: None,
+                grad: RefCell::new(None),
+                shape: self.shape().to_vec(),
+            }))
+        } else {
+            None
+        };
+    }
+
+    // Backpropagates from a single-element tensor (usually a loss) to every leaf it depends on
+    pub fn backward(&self) {
+        assert_eq!(
+            self.numel(),
+            1,
+            "Gradients can only be computed for single-element tensors."
+        );
+        let root = self
+            .node
+            .as_ref()
+            .expect("Tensor does not require grad and has no graph to backpropagate through.");
+
+        accumulate(
+            root,
+            Tensor::from_data(self.shape().to_vec(), vec![T::one()]),
+        );
+        no_grad(|| {
+            for node in topological_order(root).iter().rev() {
+                let Some(backward) = &node.backward else {
+                    continue;
+                };
+                // Intermediate gradients are released once they have been propagated
+                let Some(grad) = node.grad.borrow_mut().take() else {
+                    continue;
+                };
+                for (parent, parent_grad) in node.parents.iter().zip(backward(&grad)) {
+                    if let Some(parent) = parent {
+                        accumulate(parent, parent_grad);
+                    }
+                }
+            }
+        });
+    }
+}
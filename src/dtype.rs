@@ -0,0 +1,186 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Any value that can be stored in a tensor
+pub trait Element:
+    Copy + PartialEq + PartialOrd + Default + fmt::Debug + fmt::Display + Send + Sync + 'static
+{
+    // Name used when printing tensors, e.g. "f32"
+    const DTYPE: &'static str;
+
+    fn zero() -> Self;
+    fn one() -> Self;
+
+    // Text of the value when printing tensors, `precision` only applies to floats
+    fn format_with(self, _precision: usize) -> String {
+        self.to_string()
+    }
+}
+
+// Elements with arithmetic, used by matmul and the broadcasting operators
+pub trait Num:
+    Element + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+}
+
+// Floating point elements, the only ones that can take part in autograd
+pub trait Float: Num + Neg<Output = Self> {
+    fn from_f64(x: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn abs(self) -> Self;
+    fn tanh(self) -> Self;
+    // `ln(1 + x)` and `exp(x) - 1`, accurate for small `x`
+    fn ln_1p(self) -> Self;
+    fn exp_m1(self) -> Self;
+    // Difference between 1 and the next larger representable number
+    fn epsilon() -> Self;
+}
+
+// Conversion used by `Tensor::cast`, following the semantics of `as` for numbers.
+// Booleans convert to zero and one, numbers convert to `true` when non-zero.
+pub trait CastFrom<T> {
+    fn cast_from(value: T) -> Self;
+}
+
+macro_rules! impl_num {
+    ($($t:ty => $zero:expr, $one:expr);* $(;)?) => {
+        $(
+            impl Element for $t {
+                const DTYPE: &'static str = stringify!($t);
+
+                fn zero() -> Self {
+                    $zero
+                }
+
+                fn one() -> Self {
+                    $one
+                }
+
+                // Integers ignore the precision of the format string
+                fn format_with(self, precision: usize) -> String {
+                    format!("{:.*}", precision, self)
+                }
+            }
+
+            impl Num for $t {}
+        )*
+    };
+}
+
+impl_num! {
+    f32 => 0.0, 1.0;
+    f64 => 0.0, 1.0;
+    i32 => 0, 1;
+    i64 => 0, 1;
+}
+
+impl Element for bool {
+    const DTYPE: &'static str = "bool";
+
+    fn zero() -> Self {
+        false
+    }
+
+    fn one() -> Self {
+        true
+    }
+}
+
+macro_rules! impl_float {
+    ($($t:ty),*) => {
+        $(
+            impl Float for $t {
+                fn from_f64(x: f64) -> Self {
+                    x as $t
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn exp(self) -> Self {
+                    <$t>::exp(self)
+                }
+
+                fn ln(self) -> Self {
+                    <$t>::ln(self)
+                }
+
+                fn sqrt(self) -> Self {
+                    <$t>::sqrt(self)
+                }
+
+                fn powf(self, n: Self) -> Self {
+                    <$t>::powf(self, n)
+                }
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn tanh(self) -> Self {
+                    <$t>::tanh(self)
+                }
+
+                fn ln_1p(self) -> Self {
+                    <$t>::ln_1p(self)
+                }
+
+                fn exp_m1(self) -> Self {
+                    <$t>::exp_m1(self)
+                }
+
+                fn epsilon() -> Self {
+                    <$t>::EPSILON
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);
+
+macro_rules! impl_cast {
+    ($($from:ty),*) => {
+        $(
+            impl_cast!(@to $from => f32, f64, i32, i64);
+
+            impl CastFrom<$from> for bool {
+                fn cast_from(value: $from) -> Self {
+                    value != <$from as Element>::zero()
+                }
+            }
+
+            impl CastFrom<bool> for $from {
+                fn cast_from(value: bool) -> Self {
+                    if value {
+                        <$from as Element>::one()
+                    } else {
+                        <$from as Element>::zero()
+                    }
+                }
+            }
+        )*
+    };
+    (@to $from:ty => $($to:ty),*) => {
+        $(
+            impl CastFrom<$from> for $to {
+                fn cast_from(value: $from) -> Self {
+                    value as $to
+                }
+            }
+        )*
+    };
+}
+
+impl_cast!(f32, f64, i32, i64);
+
+impl CastFrom<bool> for bool {
+    fn cast_from(value: bool) -> Self {
+        value
+    }
+}
@@ -0,0 +1,219 @@
+use std::fmt;
+
+// Errors reported by the fallible `try_*` tensor API. The panicking methods and the
+// operator traits panic with the same messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TensorError {
+    // Operands that must agree on their shape do not
+    ShapeMismatch {
+        lhs: Vec<usize>,
+        rhs: Vec<usize>,
+    },
+    // Dimensions at `axis` of the broadcast result differ and neither of them is one
+    NotBroadcastable {
+        lhs: Vec<usize>,
+        rhs: Vec<usize>,
+        axis: usize,
+    },
+    // The number of elements is not divisible by the known dimensions of `shape`
+    InferDimension {
+        shape: Vec<usize>,
+        len: usize,
+    },
+    // More than one dimension of `shape` was marked for inference
+    TooManyInferred {
+        shape: Vec<usize>,
+    },
+    // A requested dimension is negative and not the inference marker -1
+    NegativeDimension {
+        dim: isize,
+    },
+    // The number of elements does not match the product of the shape
+    DataLength {
+        shape: Vec<usize>,
+        len: usize,
+    },
+    // The operation needs a tensor with `expected` dimensions
+    RankMismatch {
+        expected: usize,
+        shape: Vec<usize>,
+    },
+    // Only tensors with exactly one element convert to a single value
+    NotScalar {
+        shape: Vec<usize>,
+    },
+    AxisOutOfRange {
+        axis: usize,
+        ndim: usize,
+    },
+    // The axes are not a permutation of the dimensions of the tensor
+    InvalidPermutation {
+        axes: Vec<usize>,
+        ndim: usize,
+    },
+    // `index` is past the end of a dimension of the given size
+    OutOfBounds {
+        axis: usize,
+        index: isize,
+        size: usize,
+    },
+    // A slice has more indices than the tensor has dimensions
+    TooManyIndices {
+        indices: usize,
+        ndim: usize,
+    },
+    // A slice can contain at most one ellipsis
+    MultipleEllipsis,
+    // Slice steps must be positive
+    InvalidStep {
+        step: isize,
+    },
+    // A range was given a step of zero
+    ZeroStep,
+    // A range with an infinite bound would never end
+    UnboundedRange,
+    // A range has more elements than fit in memory
+    RangeTooLong,
+    // Reductions without an identity, like `max`, need at least one element per row
+    EmptyReduction {
+        axis: usize,
+    },
+    // The operation needs a square matrix
+    NotSquare {
+        shape: Vec<usize>,
+    },
+    // The matrix cannot be inverted, or has less than full rank for least squares
+    Singular,
+    // Cholesky factorization needs a symmetric positive definite matrix
+    NotPositiveDefinite,
+    // Serialized optimizer state could not be read
+    InvalidState {
+        line: usize,
+        reason: &'static str,
+    },
+    // Optimizer state has a buffer for a parameter the model does not have
+    UnknownParameter {
+        name: String,
+    },
+    // Joining needs at least one tensor
+    NoTensors,
+    // The sizes of a split do not add up to the size of the axis
+    InvalidSplit {
+        sizes: Vec<usize>,
+        size: usize,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, TensorError>;
+
+impl fmt::Display for TensorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TensorError::ShapeMismatch { lhs, rhs } => {
+                write!(f, "shapes {:?} and {:?} do not match", lhs, rhs)
+            }
+            TensorError::NotBroadcastable { lhs, rhs, axis } => write!(
+                f,
+                "shapes {:?} and {:?} could not be broadcast together at axis {}",
+                lhs, rhs, axis
+            ),
+            TensorError::InferDimension { shape, len } => write!(
+                f,
+                "cannot infer the missing dimension of {:?} from {} elements",
+                shape, len
+            ),
+            TensorError::TooManyInferred { shape } => write!(
+                f,
+                "only one dimension can be inferred, got shape {:?}",
+                shape
+            ),
+            TensorError::NegativeDimension { dim } => {
+                write!(f, "invalid dimension {}, only -1 may be negative", dim)
+            }
+            TensorError::DataLength { shape, len } => write!(
+                f,
+                "shape {:?} needs {} elements but {} were given",
+                shape,
+                shape.iter().product::<usize>(),
+                len
+            ),
+            TensorError::RankMismatch { expected, shape } => write!(
+                f,
+                "expected a tensor with {} dimensions, got shape {:?}",
+                expected, shape
+            ),
+            TensorError::NotScalar { shape } => write!(
+                f,
+                "only single-element tensors can be converted to a value, got shape {:?}",
+                shape
+            ),
+            TensorError::AxisOutOfRange { axis, ndim } => write!(
+                f,
+                "axis {} is out of range for a tensor with {} dimensions",
+                axis, ndim
+            ),
+            TensorError::InvalidPermutation { axes, ndim } => write!(
+                f,
+                "axes {:?} are not a permutation of {} dimensions",
+                axes, ndim
+            ),
+            TensorError::OutOfBounds { axis, index, size } => write!(
+                f,
+                "index {} is out of bounds for axis {} with size {}",
+                index, axis, size
+            ),
+            TensorError::TooManyIndices { indices, ndim } => write!(
+                f,
+                "too many indices for a tensor with {} dimensions, got {}",
+                ndim, indices
+            ),
+            TensorError::MultipleEllipsis => {
+                write!(f, "a slice can only contain a single ellipsis")
+            }
+            TensorError::InvalidStep { step } => {
+                write!(f, "slice step must be positive, got {}", step)
+            }
+            TensorError::ZeroStep => write!(f, "range step must not be zero"),
+            TensorError::UnboundedRange => write!(f, "range bounds must be finite"),
+            TensorError::RangeTooLong => write!(f, "range has too many elements to allocate"),
+            TensorError::EmptyReduction { axis } => write!(
+                f,
+                "cannot reduce along axis {} of size zero, the operation has no identity",
+                axis
+            ),
+            TensorError::NotSquare { shape } => {
+                write!(f, "expected a square matrix, got shape {:?}", shape)
+            }
+            TensorError::Singular => write!(f, "matrix is singular or rank deficient"),
+            TensorError::NotPositiveDefinite => {
+                write!(f, "matrix is not positive definite")
+            }
+            TensorError::InvalidState { line, reason } => {
+                write!(f, "invalid optimizer state at line {}: {}", line, reason)
+            }
+            TensorError::UnknownParameter { name } => {
+                write!(
+                    f,
+                    "optimizer state buffer {} has no matching parameter",
+                    name
+                )
+            }
+            TensorError::NoTensors => write!(f, "expected at least one tensor"),
+            TensorError::InvalidSplit { sizes, size } => {
+                write!(f, "cannot split an axis of size {} into {:?}", size, sizes)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TensorError {}
+
+// Unwraps the result of a `try_*` method for the panicking convenience API,
+// reporting the panic at the caller's location
+#[track_caller]
+pub(crate) fn unwrap<T>(result: Result<T>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => panic!("{}", err),
+    }
+}
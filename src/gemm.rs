@@ -0,0 +1,167 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::dtype::Num;
+
+// Block sizes of the packed kernel: an MC x KC block of A and a KC x NC panel of B
+// are copied into contiguous buffers so the inner loops run over unit strides and
+// stay in cache while they are reused
+const MC: usize = 64;
+const KC: usize = 256;
+const NC: usize = 512;
+
+// Products with fewer multiply-adds than this run on the calling thread only
+const PARALLEL_THRESHOLD: usize = 1 << 18;
+
+// Thread count used by `gemm`, zero means one per available core
+static NUM_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_num_threads(threads: usize) {
+    NUM_THREADS.store(threads, Ordering::Relaxed);
+}
+
+pub fn num_threads() -> usize {
+    match NUM_THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+}
+
+// A read-only strided matrix: element (i, j) is `data[offset + i * row_stride + j * col_stride]`
+#[derive(Clone, Copy)]
+pub struct MatRef<'a, T> {
+    pub data: &'a [T],
+    pub offset: usize,
+    pub row_stride: usize,
+    pub col_stride: usize,
+}
+
+impl<T: Copy> MatRef<'_, T> {
+    #[inline]
+    fn at(&self, i: usize, j: usize) -> T {
+        self.data[self.offset + i * self.row_stride + j * self.col_stride]
+    }
+}
+
+// Reference kernel: C[m x n] = A[m x k] * B[k x n] with a plain triple loop
+pub fn gemm_naive<T: Num>(a: MatRef<T>, b: MatRef<T>, c: &mut [T], m: usize, k: usize, n: usize) {
+    for i in 0..m {
+        for j in 0..n {
+            let mut sum = T::zero();
+            for p in 0..k {
+                sum = sum + a.at(i, p) * b.at(p, j);
+            }
+            c[i * n + j] = sum;
+        }
+    }
+}
+
+// C[m x n] = A[m x k] * B[k x n] where C is contiguous and row-major. Rows of C are
+// split between threads for large products, each thread runs the blocked kernel on
+// its own rows.
+pub fn gemm<T: Num>(a: MatRef<T>, b: MatRef<T>, c: &mut [T], m: usize, k: usize, n: usize) {
+    c[..m * n].fill(T::zero());
+    if m == 0 || n == 0 || k == 0 {
+        return;
+    }
+
+    let threads = if m * n * k < PARALLEL_THRESHOLD {
+        1
+    } else {
+        num_threads().min(m.div_ceil(MC / 4)).max(1)
+    };
+    if threads == 1 {
+        gemm_rows(a, b, &mut c[..m * n], 0, k, n);
+        return;
+    }
+
+    let rows_per_thread = m.div_ceil(threads);
+    thread::scope(|scope| {
+        for (t, rows) in c[..m * n].chunks_mut(rows_per_thread * n).enumerate() {
+            scope.spawn(move || gemm_rows(a, b, rows, t * rows_per_thread, k, n));
+        }
+    });
+}
+
+// Blocked product for the rows of C starting at `row0`, `c` holds those rows only
+fn gemm_rows<T: Num>(a: MatRef<T>, b: MatRef<T>, c: &mut [T], row0: usize, k: usize, n: usize) {
+    let rows = c.len() / n;
+    let mut packed_a = vec![T::zero(); MC * KC];
+    let mut packed_b = vec![T::zero(); KC * NC];
+
+    for jc in (0..n).step_by(NC) {
+        let nc = NC.min(n - jc);
+        for pc in (0..k).step_by(KC) {
+            let kc = KC.min(k - pc);
+            pack(b, pc, jc, kc, nc, &mut packed_b);
+            for ic in (0..rows).step_by(MC) {
+                let mc = MC.min(rows - ic);
+                pack(a, row0 + ic, pc, mc, kc, &mut packed_a);
+                kernel(&packed_a, &packed_b, c, (ic, jc), (mc, kc, nc), n);
+            }
+        }
+    }
+}
+
+// Copies the `rows x cols` block of `src` at (row0, col0) into `dst`, row-major
+fn pack<T: Num>(src: MatRef<T>, row0: usize, col0: usize, rows: usize, cols: usize, dst: &mut [T]) {
+    for i in 0..rows {
+        let row = &mut dst[i * cols..(i + 1) * cols];
+        for (j, x) in row.iter_mut().enumerate() {
+            *x = src.at(row0 + i, col0 + j);
+        }
+    }
+}
+
+// C[ic.., jc..] += packed A (mc x kc) * packed B (kc x nc). Four rows of C are updated
+// together so every loaded row of B is used four times, the innermost loop runs over
+// contiguous memory and vectorizes.
+fn kernel<T: Num>(
+    pa: &[T],
+    pb: &[T],
+    c: &mut [T],
+    (ic, jc): (usize, usize),
+    (mc, kc, nc): (usize, usize, usize),
+    ldc: usize,
+) {
+    let mut i = 0;
+    while i + 4 <= mc {
+        let block = &mut c[(ic + i) * ldc..(ic + i + 4) * ldc];
+        let (c0, rest) = block.split_at_mut(ldc);
+        let (c1, rest) = rest.split_at_mut(ldc);
+        let (c2, c3) = rest.split_at_mut(ldc);
+        let (c0, c1) = (&mut c0[jc..jc + nc], &mut c1[jc..jc + nc]);
+        let (c2, c3) = (&mut c2[jc..jc + nc], &mut c3[jc..jc + nc]);
+        for p in 0..kc {
+            let b_row = &pb[p * nc..(p + 1) * nc];
+            let a0 = pa[i * kc + p];
+            let a1 = pa[(i + 1) * kc + p];
+            let a2 = pa[(i + 2) * kc + p];
+            let a3 = pa[(i + 3) * kc + p];
+            let rows = c0
+                .iter_mut()
+                .zip(c1.iter_mut())
+                .zip(c2.iter_mut())
+                .zip(c3.iter_mut());
+            for ((((x0, x1), x2), x3), &b) in rows.zip(b_row) {
+                *x0 = *x0 + a0 * b;
+                *x1 = *x1 + a1 * b;
+                *x2 = *x2 + a2 * b;
+                *x3 = *x3 + a3 * b;
+            }
+        }
+        i += 4;
+    }
+
+    while i < mc {
+        let c_row = &mut c[(ic + i) * ldc + jc..(ic + i) * ldc + jc + nc];
+        for p in 0..kc {
+            let b_row = &pb[p * nc..(p + 1) * nc];
+            let a_ip = pa[i * kc + p];
+            for (x, &b) in c_row.iter_mut().zip(b_row) {
+                *x = *x + a_ip * b;
+            }
+        }
+        i += 1;
+    }
+}
@@ -0,0 +1,214 @@
+use crate::dtype::Float;
+use crate::random::Rng;
+use crate::tensor::Tensor;
+
+// Activation following a layer, used to scale the initial weights so the variance of
+// the activations is preserved through it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Nonlinearity {
+    Linear,
+    Sigmoid,
+    Tanh,
+    Relu,
+    LeakyRelu { negative_slope: f64 },
+    Selu,
+}
+
+impl Nonlinearity {
+    // The recommended gain, matching PyTorch's `calculate_gain`
+    pub fn gain(self) -> f64 {
+        match self {
+            Nonlinearity::Linear | Nonlinearity::Sigmoid => 1.0,
+            Nonlinearity::Tanh => 5.0 / 3.0,
+            Nonlinearity::Relu => 2f64.sqrt(),
+            Nonlinearity::LeakyRelu { negative_slope } => {
+                (2.0 / (1.0 + negative_slope * negative_slope)).sqrt()
+            }
+            Nonlinearity::Selu => 0.75,
+        }
+    }
+}
+
+// Which fan the Kaiming initializers keep the variance for, the inputs in the forward
+// pass or the outputs in the backward pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FanMode {
+    FanIn,
+    FanOut,
+}
+
+// Number of inputs and outputs per unit of a weight of the given shape. Weights are
+// laid out [out, in, ...] with any further dimensions forming the receptive field, a
+// vector counts its length as both fans.
+pub fn fans(shape: &[usize]) -> (usize, usize) {
+    match shape {
+        [] => (1, 1),
+        [len] => (*len, *len),
+        [out, inputs, field @ ..] => {
+            let field: usize = field.iter().product();
+            (inputs * field, out * field)
+        }
+    }
+}
+
+// A scheme for drawing initial parameter values. Layers use one by default, to override
+// it construct the layer with other schemes or refill a parameter with `Init::fill`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Init {
+    Constant(f64),
+    Uniform {
+        low: f64,
+        high: f64,
+    },
+    Normal {
+        mean: f64,
+        std: f64,
+    },
+    // Normal samples redrawn until they fall in [low, high]
+    TruncatedNormal {
+        mean: f64,
+        std: f64,
+        low: f64,
+        high: f64,
+    },
+    // Glorot: uniform in +-gain * sqrt(6 / (fan_in + fan_out))
+    XavierUniform {
+        gain: f64,
+    },
+    // Glorot: normal with std gain * sqrt(2 / (fan_in + fan_out))
+    XavierNormal {
+        gain: f64,
+    },
+    // He: uniform in +-gain * sqrt(3 / fan)
+    KaimingUniform {
+        mode: FanMode,
+        nonlinearity: Nonlinearity,
+    },
+    // He: normal with std gain / sqrt(fan)
+    KaimingNormal {
+        mode: FanMode,
+        nonlinearity: Nonlinearity,
+    },
+    // Uniform in +-sqrt(3 / fan_in), for self-normalizing networks
+    LecunUniform,
+    // Normal with std 1 / sqrt(fan_in)
+    LecunNormal,
+    // A (semi-)orthogonal matrix times `gain`, dimensions after the first are flattened
+    Orthogonal {
+        gain: f64,
+    },
+}
+
+impl Init {
+    pub fn zeros() -> Init {
+        Init::Constant(0.0)
+    }
+
+    // New tensor of the given shape
+    pub fn tensor<T: Float>(&self, shape: Vec<usize>, rng: &mut Rng) -> Tensor<T> {
+        let data = self.sample(&shape, rng);
+        Tensor::from_data(shape, data.into_iter().map(T::from_f64).collect())
+    }
+
+    // Overwrites the values of `tensor` in place, keeping it a parameter marked for
+    // autograd. The write is not recorded on the graph.
+    pub fn fill<T: Float>(&self, tensor: &mut Tensor<T>, rng: &mut Rng) {
+        let data = self.sample(tensor.shape(), rng);
+        for (x, value) in tensor.data_mut().iter_mut().zip(data) {
+            *x = T::from_f64(value);
+        }
+    }
+
+    fn sample(&self, shape: &[usize], rng: &mut Rng) -> Vec<f64> {
+        let numel: usize = shape.iter().product();
+        let (fan_in, fan_out) = fans(shape);
+        let uniform = |bound: f64, rng: &mut Rng| -> Vec<f64> {
+            (0..numel).map(|_| rng.uniform(-bound, bound)).collect()
+        };
+        let normal = |std: f64, rng: &mut Rng| -> Vec<f64> {
+            (0..numel).map(|_| std * rng.normal()).collect()
+        };
+        let kaiming_gain = |mode, nonlinearity: Nonlinearity| {
+            let fan = match mode {
+                FanMode::FanIn => fan_in,
+                FanMode::FanOut => fan_out,
+            };
+            nonlinearity.gain() / (fan.max(1) as f64).sqrt()
+        };
+        let xavier_std = |gain: f64| gain * (2.0 / (fan_in + fan_out).max(1) as f64).sqrt();
+        let lecun_std = 1.0 / (fan_in.max(1) as f64).sqrt();
+        match *self {
+            Init::Constant(value) => vec![value; numel],
+            Init::Uniform { low, high } => (0..numel).map(|_| rng.uniform(low, high)).collect(),
+            Init::Normal { mean, std } => (0..numel).map(|_| mean + std * rng.normal()).collect(),
+            Init::TruncatedNormal {
+                mean,
+                std,
+                low,
+                high,
+            } => {
+                assert!(low < high, "The truncation interval is empty.");
+                let (low, high) = ((low - mean) / std, (high - mean) / std);
+                (0..numel)
+                    .map(|_| mean + std * truncated_normal(low, high, rng))
+                    .collect()
+            }
+            Init::XavierUniform { gain } => uniform(3f64.sqrt() * xavier_std(gain), rng),
+            Init::XavierNormal { gain } => normal(xavier_std(gain), rng),
+            Init::KaimingUniform { mode, nonlinearity } => {
+                uniform(3f64.sqrt() * kaiming_gain(mode, nonlinearity), rng)
+            }
+            Init::KaimingNormal { mode, nonlinearity } => {
+                normal(kaiming_gain(mode, nonlinearity), rng)
+            }
+            Init::LecunUniform => uniform(3f64.sqrt() * lecun_std, rng),
+            Init::LecunNormal => normal(lecun_std, rng),
+            Init::Orthogonal { gain } => orthogonal(shape, gain, rng),
+        }
+    }
+}
+
+// Standard normal sample conditioned on [low, high]. Intervals around the mean are
+// sampled by rejecting normal draws, narrow or tail intervals by rejecting uniform
+// draws against the density relative to its peak in the interval.
+fn truncated_normal(low: f64, high: f64, rng: &mut Rng) -> f64 {
+    if low <= 0.0 && high >= 0.0 && high - low >= 1.0 {
+        loop {
+            let z = rng.normal();
+            if (low..=high).contains(&z) {
+                return z;
+            }
+        }
+    }
+    let peak = low.max(0.0).min(high);
+    loop {
+        let z = rng.uniform(low, high);
+        if rng.next_f64() < ((peak * peak - z * z) / 2.0).exp() {
+            return z;
+        }
+    }
+}
+
+// Q of the QR decomposition of a normal matrix, with the signs fixed by R so the
+// result is uniformly distributed. Wide matrices get orthonormal rows.
+fn orthogonal(shape: &[usize], gain: f64, rng: &mut Rng) -> Vec<f64> {
+    let numel: usize = shape.iter().product();
+    if numel == 0 {
+        return Vec::new();
+    }
+    let rows = shape.first().copied().unwrap_or(1);
+    let cols = numel / rows;
+    let (tall, short) = (rows.max(cols), rows.min(cols));
+    let (q, r) = Tensor::<f64>::randn(vec![tall, short], rng).qr();
+    let mut q = q.to_vec();
+    for j in 0..short {
+        if r[[j, j]] < 0.0 {
+            for i in 0..tall {
+                q[i * short + j] = -q[i * short + j];
+            }
+        }
+    }
+    let q = Tensor::from_data(vec![tall, short], q);
+    let q = if rows < cols { q.t() } else { q };
+    q.iter().map(|x| gain * x).collect()
+}
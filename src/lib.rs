@@ -0,0 +1,11 @@
+pub mod autograd;
+pub mod dtype;
+pub mod error;
+pub mod gemm;
+pub mod init;
+pub mod linalg;
+pub mod loss;
+pub mod nn;
+pub mod optim;
+pub mod random;
+pub mod tensor;
@@ -0,0 +1,821 @@
+use std::cmp::Ordering;
+use std::ops::{Index, IndexMut};
+
+use crate::autograd;
+use crate::dtype::Float;
+use crate::error::{self, Result, TensorError};
+use crate::tensor::Tensor;
+
+// Dense row-major matrix the factorizations work on in place
+#[derive(Clone)]
+struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T: Float> Matrix<T> {
+    fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix {
+            rows,
+            cols,
+            data: vec![T::zero(); rows * cols],
+        }
+    }
+
+    fn from_tensor(t: &Tensor<T>) -> Matrix<T> {
+        Matrix {
+            rows: t.shape()[0],
+            cols: t.shape()[1],
+            data: t.to_vec(),
+        }
+    }
+
+    fn into_tensor(self) -> Tensor<T> {
+        Tensor::from_data(vec![self.rows, self.cols], self.data)
+    }
+
+    fn transpose(&self) -> Matrix<T> {
+        let mut result = Matrix::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                result[(j, i)] = self[(i, j)];
+            }
+        }
+        result
+    }
+
+    fn matmul(&self, other: &Matrix<T>) -> Matrix<T> {
+        let mut result = Matrix::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for p in 0..self.cols {
+                let a = self[(i, p)];
+                for j in 0..other.cols {
+                    result[(i, j)] = result[(i, j)] + a * other[(p, j)];
+                }
+            }
+        }
+        result
+    }
+
+    fn max_abs(&self) -> T {
+        self.data
+            .iter()
+            .fold(T::zero(), |m, &x| if x.abs() > m { x.abs() } else { m })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+// Pivots at or below this size count as zero when deciding whether a matrix is
+// singular or rank deficient, relative to the largest entry like in NumPy
+fn tolerance<T: Float>(scale: T, size: usize) -> T {
+    scale * T::epsilon() * T::from_f64(size as f64)
+}
+
+// LU factorization with partial pivoting, PA = LU. The strict lower triangle of `lu`
+// holds L without its unit diagonal and the upper triangle holds U. Row i of PA is
+// row `perm[i]` of A.
+struct Lu<T> {
+    lu: Matrix<T>,
+    perm: Vec<usize>,
+    // Parity of the row swaps, the sign of det(P)
+    sign: T,
+    singular: bool,
+}
+
+impl<T: Float> Lu<T> {
+    fn new(mut lu: Matrix<T>) -> Lu<T> {
+        let n = lu.rows;
+        let tol = tolerance(lu.max_abs(), n);
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut sign = T::one();
+        let mut singular = false;
+        for k in 0..n {
+            let p = (k..n).fold(k, |best, i| {
+                if lu[(i, k)].abs() > lu[(best, k)].abs() {
+                    i
+                } else {
+                    best
+                }
+            });
+            if lu[(p, k)].abs() <= tol {
+                singular = true;
+            }
+            // The column is already eliminated, L gets zeros below the diagonal
+            if lu[(p, k)] == T::zero() {
+                continue;
+            }
+            if p != k {
+                for j in 0..n {
+                    lu.data.swap(k * n + j, p * n + j);
+                }
+                perm.swap(k, p);
+                sign = -sign;
+            }
+            for i in k + 1..n {
+                let factor = lu[(i, k)] / lu[(k, k)];
+                lu[(i, k)] = factor;
+                for j in k + 1..n {
+                    lu[(i, j)] = lu[(i, j)] - factor * lu[(k, j)];
+                }
+            }
+        }
+        Lu {
+            lu,
+            perm,
+            sign,
+            singular,
+        }
+    }
+
+    // Solves AX = B for every column of B
+    fn solve(&self, b: &Matrix<T>) -> Matrix<T> {
+        let n = self.lu.rows;
+        let mut x = Matrix::zeros(n, b.cols);
+        for i in 0..n {
+            for j in 0..b.cols {
+                x[(i, j)] = b[(self.perm[i], j)];
+            }
+        }
+        for j in 0..b.cols {
+            // Forward substitution with the unit lower triangle, then back substitution
+            for i in 0..n {
+                let mut y = x[(i, j)];
+                for p in 0..i {
+                    y = y - self.lu[(i, p)] * x[(p, j)];
+                }
+                x[(i, j)] = y;
+            }
+            for i in (0..n).rev() {
+                let mut y = x[(i, j)];
+                for p in i + 1..n {
+                    y = y - self.lu[(i, p)] * x[(p, j)];
+                }
+                x[(i, j)] = y / self.lu[(i, i)];
+            }
+        }
+        x
+    }
+}
+
+// Householder QR of an m x n matrix in reduced form: Q is m x k with orthonormal
+// columns and R is k x n upper triangular, k = min(m, n)
+fn householder_qr<T: Float>(a: &Matrix<T>) -> (Matrix<T>, Matrix<T>) {
+    let (m, n) = (a.rows, a.cols);
+    let k = m.min(n);
+    let mut r = a.clone();
+    let mut reflectors = Vec::with_capacity(k);
+    for j in 0..k {
+        let mut v: Vec<T> = (j..m).map(|i| r[(i, j)]).collect();
+        let norm = v.iter().fold(T::zero(), |s, &x| s + x * x).sqrt();
+        // Reflect onto -sign(x0) * |x| e0 so the first entry does not cancel
+        let alpha = if v[0] > T::zero() { -norm } else { norm };
+        v[0] = v[0] - alpha;
+        let v_norm2 = v.iter().fold(T::zero(), |s, &x| s + x * x);
+        if v_norm2 != T::zero() {
+            apply_reflector(&mut r, &v, v_norm2, j, j);
+        }
+        reflectors.push((v, v_norm2));
+    }
+
+    // Q is the product of the reflectors applied to the first k columns of I
+    let mut q = Matrix::zeros(m, k);
+    for i in 0..k {
+        q[(i, i)] = T::one();
+    }
+    for (j, (v, v_norm2)) in reflectors.iter().enumerate().rev() {
+        if *v_norm2 != T::zero() {
+            apply_reflector(&mut q, v, *v_norm2, j, 0);
+        }
+    }
+
+    let mut upper = Matrix::zeros(k, n);
+    for i in 0..k {
+        for j in i..n {
+            upper[(i, j)] = r[(i, j)];
+        }
+    }
+    (q, upper)
+}
+
+// Applies H = I - 2 v v^T / |v|^2 to rows `row0..` and columns `col0..` of `a`
+fn apply_reflector<T: Float>(a: &mut Matrix<T>, v: &[T], v_norm2: T, row0: usize, col0: usize) {
+    let two = T::one() + T::one();
+    for j in col0..a.cols {
+        let dot = v
+            .iter()
+            .enumerate()
+            .fold(T::zero(), |s, (i, &vi)| s + vi * a[(row0 + i, j)]);
+        let scale = two * dot / v_norm2;
+        for (i, &vi) in v.iter().enumerate() {
+            a[(row0 + i, j)] = a[(row0 + i, j)] - scale * vi;
+        }
+    }
+}
+
+// Solves RX = B (or R^T X = B with `transposed`) for an upper triangular R
+fn solve_triangular<T: Float>(r: &Matrix<T>, b: &Matrix<T>, transposed: bool) -> Matrix<T> {
+    let n = r.rows;
+    let mut x = b.clone();
+    for j in 0..b.cols {
+        for step in 0..n {
+            // R^T is lower triangular and solved from the top, R from the bottom
+            let (i, known) = if transposed {
+                (step, 0..step)
+            } else {
+                (n - 1 - step, n - step..n)
+            };
+            let mut y = x[(i, j)];
+            for p in known {
+                let coef = if transposed { r[(p, i)] } else { r[(i, p)] };
+                y = y - coef * x[(p, j)];
+            }
+            x[(i, j)] = y / r[(i, i)];
+        }
+    }
+    x
+}
+
+// Whether the diagonal of a triangular factor has an entry that counts as zero
+fn rank_deficient<T: Float>(r: &Matrix<T>, size: usize) -> bool {
+    let k = r.rows.min(r.cols);
+    let scale = (0..k).fold(T::zero(), |m, i| {
+        if r[(i, i)].abs() > m {
+            r[(i, i)].abs()
+        } else {
+            m
+        }
+    });
+    let tol = tolerance(scale, size);
+    (0..k).any(|i| r[(i, i)].abs() <= tol)
+}
+
+// Upper bound on the number of Jacobi sweeps, they converge quadratically and
+// usually need fewer than ten
+const MAX_SWEEPS: usize = 64;
+
+// Rotation (c, s) that zeroes the off-diagonal entry of the symmetric 2 x 2 matrix
+// [[app, apq], [apq, aqq]], taking the smaller of the two possible angles
+fn jacobi_rotation<T: Float>(app: T, aqq: T, apq: T) -> (T, T) {
+    let two = T::one() + T::one();
+    let theta = (aqq - app) / (two * apq);
+    let magnitude = T::one() / (theta.abs() + (theta * theta + T::one()).sqrt());
+    let t = if theta < T::zero() {
+        -magnitude
+    } else {
+        magnitude
+    };
+    let c = T::one() / (t * t + T::one()).sqrt();
+    (c, t * c)
+}
+
+// Replaces columns p and q of `m` with c * p - s * q and s * p + c * q
+fn rotate_columns<T: Float>(m: &mut Matrix<T>, p: usize, q: usize, c: T, s: T) {
+    for i in 0..m.rows {
+        let (x, y) = (m[(i, p)], m[(i, q)]);
+        m[(i, p)] = c * x - s * y;
+        m[(i, q)] = s * x + c * y;
+    }
+}
+
+// Eigenvalues and eigenvectors (as columns) of a symmetric matrix by the cyclic
+// Jacobi method, which rotates the off-diagonal entries to zero one pair at a time
+fn symmetric_eigen<T: Float>(mut a: Matrix<T>) -> (Vec<T>, Matrix<T>) {
+    let n = a.rows;
+    let mut v = Matrix::zeros(n, n);
+    for i in 0..n {
+        v[(i, i)] = T::one();
+    }
+    let total = a.data.iter().fold(T::zero(), |s, &x| s + x * x);
+    let threshold = T::epsilon() * T::epsilon() * total;
+    for _ in 0..MAX_SWEEPS {
+        let mut off = T::zero();
+        for p in 0..n {
+            for q in p + 1..n {
+                off = off + a[(p, q)] * a[(p, q)];
+            }
+        }
+        if off <= threshold {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[(p, q)] == T::zero() {
+                    continue;
+                }
+                let (c, s) = jacobi_rotation(a[(p, p)], a[(q, q)], a[(p, q)]);
+                // A becomes J^T A J, applied to the columns and then to the rows
+                rotate_columns(&mut a, p, q, c, s);
+                for j in 0..n {
+                    let (x, y) = (a[(p, j)], a[(q, j)]);
+                    a[(p, j)] = c * x - s * y;
+                    a[(q, j)] = s * x + c * y;
+                }
+                rotate_columns(&mut v, p, q, c, s);
+            }
+        }
+    }
+    ((0..n).map(|i| a[(i, i)]).collect(), v)
+}
+
+// Thin SVD of an m x n matrix with m >= n by the one-sided Jacobi method, which
+// rotates pairs of columns until all of them are orthogonal. Returns the singular
+// values, the m x n matrix of left vectors and the n x n matrix of right vectors.
+// Left vectors of zero singular values are left as zero columns.
+fn one_sided_jacobi<T: Float>(mut u: Matrix<T>) -> (Vec<T>, Matrix<T>, Matrix<T>) {
+    let n = u.cols;
+    let mut v = Matrix::zeros(n, n);
+    for i in 0..n {
+        v[(i, i)] = T::one();
+    }
+    let column_dot = |u: &Matrix<T>, p: usize, q: usize| {
+        (0..u.rows).fold(T::zero(), |s, i| s + u[(i, p)] * u[(i, q)])
+    };
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let alpha = column_dot(&u, p, p);
+                let beta = column_dot(&u, q, q);
+                let gamma = column_dot(&u, p, q);
+                if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let (c, s) = jacobi_rotation(alpha, beta, gamma);
+                rotate_columns(&mut u, p, q, c, s);
+                rotate_columns(&mut v, p, q, c, s);
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+    let mut sigma = Vec::with_capacity(n);
+    for j in 0..n {
+        let norm = column_dot(&u, j, j).sqrt();
+        for i in 0..u.rows {
+            u[(i, j)] = if norm > T::zero() {
+                u[(i, j)] / norm
+            } else {
+                T::zero()
+            };
+        }
+        sigma.push(norm);
+    }
+    (sigma, u, v)
+}
+
+// Reorders the columns of `m` so that column j is the old column `order[j]`, keeping
+// the first `cols` columns
+fn select_columns<T: Float>(m: &Matrix<T>, order: &[usize], cols: usize) -> Matrix<T> {
+    let mut result = Matrix::zeros(m.rows, cols);
+    for (j, &k) in order.iter().take(cols).enumerate() {
+        for i in 0..m.rows {
+            result[(i, j)] = m[(i, k)];
+        }
+    }
+    result
+}
+
+// Fills the columns of `m` that are not `valid` with unit vectors orthogonal to all
+// other columns, by orthogonalizing standard basis vectors with Gram-Schmidt
+fn complete_basis<T: Float>(m: &mut Matrix<T>, mut valid: Vec<bool>) {
+    let half = T::one() / (T::one() + T::one());
+    let mut candidates = 0..m.rows;
+    for j in 0..m.cols {
+        if valid[j] {
+            continue;
+        }
+        for e in candidates.by_ref() {
+            let mut x = vec![T::zero(); m.rows];
+            x[e] = T::one();
+            // Twice, so the result is orthogonal to working precision
+            for _ in 0..2 {
+                for k in (0..m.cols).filter(|&k| valid[k]) {
+                    let dot = (0..m.rows).fold(T::zero(), |s, i| s + m[(i, k)] * x[i]);
+                    for (i, xi) in x.iter_mut().enumerate() {
+                        *xi = *xi - dot * m[(i, k)];
+                    }
+                }
+            }
+            let norm = x.iter().fold(T::zero(), |s, &xi| s + xi * xi).sqrt();
+            // Basis vectors mostly inside the span so far are skipped
+            if norm > half {
+                for (i, xi) in x.into_iter().enumerate() {
+                    m[(i, j)] = xi / norm;
+                }
+                valid[j] = true;
+                break;
+            }
+        }
+    }
+}
+
+// Whether column j of `m` has to be negated so that its entry of largest magnitude
+// is positive, which fixes the sign of eigenvectors and singular vectors
+fn flip_sign<T: Float>(m: &Matrix<T>, j: usize) -> bool {
+    let mut largest = T::zero();
+    for i in 0..m.rows {
+        if m[(i, j)].abs() > largest.abs() {
+            largest = m[(i, j)];
+        }
+    }
+    largest < T::zero()
+}
+
+fn negate_column<T: Float>(m: &mut Matrix<T>, j: usize) {
+    for i in 0..m.rows {
+        m[(i, j)] = -m[(i, j)];
+    }
+}
+
+impl<T: Float> Tensor<T> {
+    fn check_matrix(&self) -> Result<()> {
+        if self.ndim() != 2 {
+            return Err(TensorError::RankMismatch {
+                expected: 2,
+                shape: self.shape().to_vec(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_square(&self) -> Result<()> {
+        self.check_matrix()?;
+        if self.shape()[0] != self.shape()[1] {
+            return Err(TensorError::NotSquare {
+                shape: self.shape().to_vec(),
+            });
+        }
+        Ok(())
+    }
+
+    // Right-hand side of `solve` and `lstsq` as a matrix with `rows` rows, a vector is
+    // treated as a single column
+    fn rhs_matrix(&self, b: &Tensor<T>) -> Result<Matrix<T>> {
+        let rows = self.shape()[0];
+        match b.shape() {
+            [n] if *n == rows => Ok(Matrix {
+                rows,
+                cols: 1,
+                data: b.to_vec(),
+            }),
+            [n, _] if *n == rows => Ok(Matrix::from_tensor(b)),
+            _ => Err(TensorError::ShapeMismatch {
+                lhs: self.shape().to_vec(),
+                rhs: b.shape().to_vec(),
+            }),
+        }
+    }
+
+    // ========================================================================
+    // Decompositions
+    // These are not recorded on the graph.
+    // LU factorization with partial pivoting of a square matrix, returns (P, L, U)
+    // with A = PLU, L unit lower triangular and U upper triangular
+    #[track_caller]
+    pub fn lu(&self) -> (Tensor<T>, Tensor<T>, Tensor<T>) {
+        error::unwrap(self.try_lu())
+    }
+
+    pub fn try_lu(&self) -> Result<(Tensor<T>, Tensor<T>, Tensor<T>)> {
+        self.check_square()?;
+        let n = self.shape()[0];
+        let factors = Lu::new(Matrix::from_tensor(self));
+        let (mut p, mut l, mut u) = (
+            Matrix::zeros(n, n),
+            Matrix::zeros(n, n),
+            Matrix::zeros(n, n),
+        );
+        for i in 0..n {
+            // Row i of PA is row perm[i] of A, so P has its ones at (perm[i], i)
+            p[(factors.perm[i], i)] = T::one();
+            l[(i, i)] = T::one();
+            for j in 0..n {
+                if j < i {
+                    l[(i, j)] = factors.lu[(i, j)];
+                } else {
+                    u[(i, j)] = factors.lu[(i, j)];
+                }
+            }
+        }
+        Ok((p.into_tensor(), l.into_tensor(), u.into_tensor()))
+    }
+
+    // Reduced QR decomposition of an m x n matrix by Householder reflections, returns
+    // (Q, R) with Q of shape [m, k] with orthonormal columns, R of shape [k, n] upper
+    // triangular and k = min(m, n)
+    #[track_caller]
+    pub fn qr(&self) -> (Tensor<T>, Tensor<T>) {
+        error::unwrap(self.try_qr())
+    }
+
+    pub fn try_qr(&self) -> Result<(Tensor<T>, Tensor<T>)> {
+        self.check_matrix()?;
+        let (q, r) = householder_qr(&Matrix::from_tensor(self));
+        Ok((q.into_tensor(), r.into_tensor()))
+    }
+
+    // Lower triangular L with A = LL^T for a symmetric positive definite matrix. Only
+    // the lower triangle of A is read.
+    #[track_caller]
+    pub fn cholesky(&self) -> Tensor<T> {
+        error::unwrap(self.try_cholesky())
+    }
+
+    pub fn try_cholesky(&self) -> Result<Tensor<T>> {
+        self.check_square()?;
+        let a = Matrix::from_tensor(self);
+        let n = a.rows;
+        let mut l = Matrix::zeros(n, n);
+        for j in 0..n {
+            let mut d = a[(j, j)];
+            for p in 0..j {
+                d = d - l[(j, p)] * l[(j, p)];
+            }
+            // Written so that NaN is also rejected
+            if d.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+                return Err(TensorError::NotPositiveDefinite);
+            }
+            l[(j, j)] = d.sqrt();
+            for i in j + 1..n {
+                let mut s = a[(i, j)];
+                for p in 0..j {
+                    s = s - l[(i, p)] * l[(j, p)];
+                }
+                l[(i, j)] = s / l[(j, j)];
+            }
+        }
+        Ok(l.into_tensor())
+    }
+
+    // ========================================================================
+    // Solvers
+    // Solution X of AX = B for a square, invertible A, where B is a vector of shape
+    // [n] or a matrix of shape [n, k]
+    #[track_caller]
+    pub fn solve(&self, b: &Tensor<T>) -> Tensor<T> {
+        error::unwrap(self.try_solve(b))
+    }
+
+    pub fn try_solve(&self, b: &Tensor<T>) -> Result<Tensor<T>> {
+        self.check_square()?;
+        let rhs = self.rhs_matrix(b)?;
+        let factors = Lu::new(Matrix::from_tensor(self));
+        if factors.singular {
+            return Err(TensorError::Singular);
+        }
+        let x = factors.solve(&rhs);
+        let result = Tensor::from_data(b.shape().to_vec(), x.data);
+
+        let (a, x) = (self.detach(), result.detach());
+        Ok(autograd::record(result, &[self, b], move |grad| {
+            // dB = A^-T G and dA = -dB X^T
+            let grad_b = a.t().solve(grad);
+            let column = |t: &Tensor<T>| {
+                if t.ndim() == 1 {
+                    t.unsqueeze(1)
+                } else {
+                    t.clone()
+                }
+            };
+            let grad_a = -column(&grad_b).matmul(&column(&x).t());
+            vec![grad_a, grad_b]
+        }))
+    }
+
+    // Least squares solution X minimizing |AX - B| for an m x n matrix A of full rank.
+    // Overdetermined systems (m >= n) get the best fit, underdetermined ones the
+    // solution of minimum norm. Not recorded on the graph.
+    #[track_caller]
+    pub fn lstsq(&self, b: &Tensor<T>) -> Tensor<T> {
+        error::unwrap(self.try_lstsq(b))
+    }
+
+    pub fn try_lstsq(&self, b: &Tensor<T>) -> Result<Tensor<T>> {
+        self.check_matrix()?;
+        let rhs = self.rhs_matrix(b)?;
+        let a = Matrix::from_tensor(self);
+        let (m, n) = (a.rows, a.cols);
+        let x = if m >= n {
+            // A = QR, so R X = Q^T B
+            let (q, r) = householder_qr(&a);
+            if rank_deficient(&r, m) {
+                return Err(TensorError::Singular);
+            }
+            solve_triangular(&r, &q.transpose().matmul(&rhs), false)
+        } else {
+            // A^T = QR, so A = R^T Q^T and X = Q Y with R^T Y = B lies in the row space
+            let (q, r) = householder_qr(&a.transpose());
+            if rank_deficient(&r, n) {
+                return Err(TensorError::Singular);
+            }
+            q.matmul(&solve_triangular(&r, &rhs, true))
+        };
+        let shape = if b.ndim() == 1 {
+            vec![n]
+        } else {
+            vec![n, rhs.cols]
+        };
+        Tensor::try_from_data(shape, x.data)
+    }
+
+    // ========================================================================
+    // Inverses and determinants
+    // Inverse of a square, invertible matrix
+    #[track_caller]
+    pub fn inv(&self) -> Tensor<T> {
+        error::unwrap(self.try_inv())
+    }
+
+    pub fn try_inv(&self) -> Result<Tensor<T>> {
+        self.check_square()?;
+        self.try_solve(&Tensor::eye(self.shape()[0]))
+    }
+
+    // Determinant of a square matrix as a 0-D tensor, zero for singular matrices.
+    // The gradient needs an invertible matrix.
+    #[track_caller]
+    pub fn det(&self) -> Tensor<T> {
+        error::unwrap(self.try_det())
+    }
+
+    pub fn try_det(&self) -> Result<Tensor<T>> {
+        self.check_square()?;
+        let factors = Lu::new(Matrix::from_tensor(self));
+        let n = self.shape()[0];
+        let det = (0..n).fold(factors.sign, |d, i| d * factors.lu[(i, i)]);
+        let result = Tensor::scalar(det);
+
+        let a = self.detach();
+        Ok(autograd::record(result, &[self], move |grad| {
+            // d det(A) / dA = det(A) A^-T
+            let scale = grad.item() * det;
+            vec![a.inv().t().map(|x| x * scale)]
+        }))
+    }
+
+    // Sign and natural logarithm of the absolute value of the determinant as 0-D
+    // tensors, which does not overflow for large matrices. A singular matrix gives a
+    // sign of zero and a logarithm of negative infinity. Only the logarithm is
+    // recorded on the graph.
+    #[track_caller]
+    pub fn slogdet(&self) -> (Tensor<T>, Tensor<T>) {
+        error::unwrap(self.try_slogdet())
+    }
+
+    pub fn try_slogdet(&self) -> Result<(Tensor<T>, Tensor<T>)> {
+        self.check_square()?;
+        let factors = Lu::new(Matrix::from_tensor(self));
+        let n = self.shape()[0];
+        let mut sign = factors.sign;
+        let mut log_abs = T::zero();
+        for i in 0..n {
+            let u = factors.lu[(i, i)];
+            if u == T::zero() {
+                sign = T::zero();
+                log_abs = T::from_f64(f64::NEG_INFINITY);
+                break;
+            }
+            if u < T::zero() {
+                sign = -sign;
+            }
+            log_abs = log_abs + u.abs().ln();
+        }
+        let result = Tensor::scalar(log_abs);
+
+        let a = self.detach();
+        let log_abs = autograd::record(result, &[self], move |grad| {
+            // d log|det(A)| / dA = A^-T
+            let g = grad.item();
+            vec![a.inv().t().map(|x| x * g)]
+        });
+        Ok((Tensor::scalar(sign), log_abs))
+    }
+
+    // ========================================================================
+    // Spectral decompositions
+    // These are not recorded on the graph. Vectors are normalized so that their entry
+    // of largest magnitude is positive, making the results deterministic.
+    // Eigenvalues in ascending order and the matching eigenvectors as the columns of
+    // an [n, n] matrix for a symmetric matrix A, so A = V diag(w) V^T. Only the lower
+    // triangle of A is read.
+    #[track_caller]
+    pub fn eigh(&self) -> (Tensor<T>, Tensor<T>) {
+        error::unwrap(self.try_eigh())
+    }
+
+    pub fn try_eigh(&self) -> Result<(Tensor<T>, Tensor<T>)> {
+        self.check_square()?;
+        let mut a = Matrix::from_tensor(self);
+        let n = a.rows;
+        for i in 0..n {
+            for j in i + 1..n {
+                a[(i, j)] = a[(j, i)];
+            }
+        }
+        let (values, vectors) = symmetric_eigen(a);
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| values[i].partial_cmp(&values[j]).unwrap_or(Ordering::Equal));
+        let mut vectors = select_columns(&vectors, &order, n);
+        for j in 0..n {
+            if flip_sign(&vectors, j) {
+                negate_column(&mut vectors, j);
+            }
+        }
+        let values = order.iter().map(|&i| values[i]).collect();
+        Ok((Tensor::from_data(vec![n], values), vectors.into_tensor()))
+    }
+
+    // Singular value decomposition A = U diag(S) Vt of an m x n matrix, with the
+    // singular values S in descending order. The thin form has U of shape [m, k] and
+    // Vt of shape [k, n] with k = min(m, n), `full_matrices` extends U and Vt to
+    // square orthogonal matrices.
+    #[track_caller]
+    pub fn svd(&self, full_matrices: bool) -> (Tensor<T>, Tensor<T>, Tensor<T>) {
+        error::unwrap(self.try_svd(full_matrices))
+    }
+
+    pub fn try_svd(&self, full_matrices: bool) -> Result<(Tensor<T>, Tensor<T>, Tensor<T>)> {
+        self.check_matrix()?;
+        let a = Matrix::from_tensor(self);
+        let (m, n) = (a.rows, a.cols);
+        // A wide matrix is decomposed through its transpose, A^T = V S U^T
+        let wide = m < n;
+        let tall = if wide { a.transpose() } else { a };
+        let (sigma, left, right) = one_sided_jacobi(tall);
+        let (rows, k) = (left.rows, left.cols);
+
+        let mut order: Vec<usize> = (0..k).collect();
+        order.sort_by(|&i, &j| sigma[j].partial_cmp(&sigma[i]).unwrap_or(Ordering::Equal));
+        let sigma: Vec<T> = order.iter().map(|&i| sigma[i]).collect();
+        // The long side gets an orthonormal basis also where the singular value is zero,
+        // completed to a square matrix for the full form
+        let long_cols = if full_matrices { rows } else { k };
+        let mut long = select_columns(&left, &order, long_cols);
+        let valid = (0..long_cols)
+            .map(|j| j < k && sigma[j] > T::zero())
+            .collect();
+        complete_basis(&mut long, valid);
+        let short = select_columns(&right, &order, k);
+
+        let (mut u, mut v) = if wide { (short, long) } else { (long, short) };
+        for j in 0..u.cols {
+            if flip_sign(&u, j) {
+                negate_column(&mut u, j);
+                if j < v.cols {
+                    negate_column(&mut v, j);
+                }
+            }
+        }
+        // Right vectors without a left partner only exist in the full form of a wide
+        // matrix and follow the same convention
+        for j in u.cols..v.cols {
+            if flip_sign(&v, j) {
+                negate_column(&mut v, j);
+            }
+        }
+        Ok((
+            u.into_tensor(),
+            Tensor::from_data(vec![k], sigma),
+            v.transpose().into_tensor(),
+        ))
+    }
+
+    // Moore-Penrose pseudo-inverse of an m x n matrix of any rank, shape [n, m], from
+    // its SVD. Singular values up to `rcond` times the largest one count as zero,
+    // `None` uses max(m, n) times the machine epsilon. Not recorded on the graph.
+    #[track_caller]
+    pub fn pinv(&self, rcond: Option<T>) -> Tensor<T> {
+        error::unwrap(self.try_pinv(rcond))
+    }
+
+    pub fn try_pinv(&self, rcond: Option<T>) -> Result<Tensor<T>> {
+        let (u, sigma, vt) = self.try_svd(false)?;
+        let (m, n) = (self.shape()[0], self.shape()[1]);
+        let rcond = rcond.unwrap_or_else(|| tolerance(T::one(), m.max(n)));
+        // Descending order puts the largest singular value first
+        let cutoff = sigma
+            .iter()
+            .next()
+            .map_or(T::zero(), |largest| rcond * largest);
+        let inverse = sigma.map(|x| if x > cutoff { T::one() / x } else { T::zero() });
+        // V diag(1 / S) U^T
+        (vt.t() * inverse).try_matmul(&u.t())
+    }
+}
@@ -0,0 +1,386 @@
+use crate::dtype::Float;
+use crate::error::{self, Result, TensorError};
+use crate::tensor::Tensor;
+
+// How the per-element losses are combined into the result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduction {
+    // Keep one loss per element, or per sample for the classification losses
+    None,
+    Mean,
+    Sum,
+}
+
+fn reduce<T: Float>(loss: Tensor<T>, reduction: Reduction) -> Tensor<T> {
+    match reduction {
+        Reduction::None => loss,
+        Reduction::Mean => loss.mean_all(),
+        Reduction::Sum => loss.sum_all(),
+    }
+}
+
+// Unlike the arithmetic operators, losses do not broadcast a target of another shape
+fn check_shapes<T: Float>(input: &Tensor<T>, target: &Tensor<T>) -> Result<()> {
+    if input.shape() != target.shape() {
+        return Err(TensorError::ShapeMismatch {
+            lhs: input.shape().to_vec(),
+            rhs: target.shape().to_vec(),
+        });
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Regression
+
+// Mean squared error, (input - target)^2
+#[track_caller]
+pub fn mse<T: Float>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction) -> Tensor<T> {
+    error::unwrap(try_mse(input, target, reduction))
+}
+
+pub fn try_mse<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    check_shapes(input, target)?;
+    let diff = input - target;
+    Ok(reduce(&diff * &diff, reduction))
+}
+
+// Mean absolute error, |input - target|
+#[track_caller]
+pub fn mae<T: Float>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction) -> Tensor<T> {
+    error::unwrap(try_mae(input, target, reduction))
+}
+
+pub fn try_mae<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    check_shapes(input, target)?;
+    Ok(reduce((input - target).abs(), reduction))
+}
+
+// Quadratic for errors up to `delta` and linear beyond, so outliers pull less than
+// with `mse`: d^2 / 2 if |d| < delta, else delta * (|d| - delta / 2)
+#[track_caller]
+pub fn huber<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    delta: T,
+    reduction: Reduction,
+) -> Tensor<T> {
+    error::unwrap(try_huber(input, target, delta, reduction))
+}
+
+pub fn try_huber<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    delta: T,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    check_shapes(input, target)?;
+    let half = T::from_f64(0.5);
+    let diff = input - target;
+    let abs = diff.abs();
+    let quadratic = &diff * &diff * half;
+    let linear = (&abs - delta * half) * delta;
+    let small = abs.lt(&Tensor::scalar(delta));
+    Ok(reduce(
+        Tensor::where_(&small, &quadratic, &linear),
+        reduction,
+    ))
+}
+
+// Huber loss divided by `beta`, which keeps the slope of the linear part at one.
+// Equals `mae` for a `beta` of zero.
+#[track_caller]
+pub fn smooth_l1<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    beta: T,
+    reduction: Reduction,
+) -> Tensor<T> {
+    error::unwrap(try_smooth_l1(input, target, beta, reduction))
+}
+
+pub fn try_smooth_l1<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    beta: T,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    if beta == T::zero() {
+        return try_mae(input, target, reduction);
+    }
+    let loss = try_huber(input, target, beta, Reduction::None)?;
+    Ok(reduce(loss / beta, reduction))
+}
+
+// ============================================================================
+// Binary classification
+
+// Cross-entropy between predicted probabilities and targets in [0, 1]:
+// -(target * ln(input) + (1 - target) * ln(1 - input))
+// The logarithms are clamped to -100 so confident mistakes stay finite.
+#[track_caller]
+pub fn binary_cross_entropy<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    reduction: Reduction,
+) -> Tensor<T> {
+    error::unwrap(try_binary_cross_entropy(input, target, reduction))
+}
+
+pub fn try_binary_cross_entropy<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    check_shapes(input, target)?;
+    let floor = T::from_f64(-100.0);
+    let log = |x: &Tensor<T>| x.ln().clamp(floor, T::zero());
+    let one = T::one();
+    let positive = target * log(input);
+    let negative = -(target - one) * log(&-(input - one));
+    Ok(reduce(-(positive + negative), reduction))
+}
+
+// `binary_cross_entropy` of `sigmoid(input)`, computed from the logits directly which is
+// stable for large magnitudes: (1 - target) * input + softplus(-input)
+#[track_caller]
+pub fn binary_cross_entropy_with_logits<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    reduction: Reduction,
+) -> Tensor<T> {
+    error::unwrap(try_binary_cross_entropy_with_logits(
+        input, target, reduction,
+    ))
+}
+
+pub fn try_binary_cross_entropy_with_logits<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    check_shapes(input, target)?;
+    let loss = input - input * target + (-input).softplus();
+    Ok(reduce(loss, reduction))
+}
+
+// Hinge loss for targets of -1 or 1, max(0, margin - input * target)
+#[track_caller]
+pub fn hinge<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    margin: T,
+    reduction: Reduction,
+) -> Tensor<T> {
+    error::unwrap(try_hinge(input, target, margin, reduction))
+}
+
+pub fn try_hinge<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    margin: T,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    check_shapes(input, target)?;
+    Ok(reduce((-(input * target) + margin).relu(), reduction))
+}
+
+// ============================================================================
+// Multi-class classification
+
+// Per-sample losses of class log-probabilities `log_probs` [N, C] against the class
+// indices `target` [N], and the total weight of the targets that `Reduction::Mean`
+// divides by. With `label_smoothing` the target distribution puts
+// `label_smoothing / C` on every class and the rest on the target class.
+fn class_losses<T: Float>(
+    log_probs: &Tensor<T>,
+    target: &Tensor<i64>,
+    weight: Option<&Tensor<T>>,
+    label_smoothing: T,
+) -> Result<(Tensor<T>, T)> {
+    if log_probs.ndim() != 2 {
+        return Err(TensorError::RankMismatch {
+            expected: 2,
+            shape: log_probs.shape().to_vec(),
+        });
+    }
+    let (samples, classes) = (log_probs.shape()[0], log_probs.shape()[1]);
+    if target.shape() != [samples] {
+        return Err(TensorError::ShapeMismatch {
+            lhs: log_probs.shape().to_vec(),
+            rhs: target.shape().to_vec(),
+        });
+    }
+    let class_weight = match weight {
+        Some(weight) if weight.shape() != [classes] => {
+            return Err(TensorError::ShapeMismatch {
+                lhs: log_probs.shape().to_vec(),
+                rhs: weight.shape().to_vec(),
+            })
+        }
+        Some(weight) => weight.to_vec(),
+        None => vec![T::one(); classes],
+    };
+
+    // The weighted target distribution of every sample, constant for the graph
+    let smooth = label_smoothing / T::from_f64(classes as f64);
+    let mut mask = Vec::with_capacity(samples * classes);
+    let mut total = T::zero();
+    for class in target.iter() {
+        if class < 0 || class as usize >= classes {
+            return Err(TensorError::OutOfBounds {
+                axis: 1,
+                index: class as isize,
+                size: classes,
+            });
+        }
+        let class = class as usize;
+        total = total + class_weight[class];
+        mask.extend(class_weight.iter().enumerate().map(|(c, &w)| {
+            let on_target = if c == class {
+                T::one() - label_smoothing
+            } else {
+                T::zero()
+            };
+            w * (on_target + smooth)
+        }));
+    }
+    let mask = Tensor::from_data(vec![samples, classes], mask);
+    Ok((-(log_probs * &mask).sum(1, false), total))
+}
+
+fn reduce_weighted<T: Float>(loss: Tensor<T>, total: T, reduction: Reduction) -> Tensor<T> {
+    match reduction {
+        Reduction::Mean => loss.sum_all() / total,
+        _ => reduce(loss, reduction),
+    }
+}
+
+// Negative log-likelihood of log-probabilities `input` [N, C] for the class indices
+// `target` [N]. Classes are scaled by `weight` [C] if given, the mean is then taken
+// over the total weight of the targets.
+#[track_caller]
+pub fn nll<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<i64>,
+    weight: Option<&Tensor<T>>,
+    reduction: Reduction,
+) -> Tensor<T> {
+    error::unwrap(try_nll(input, target, weight, reduction))
+}
+
+pub fn try_nll<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<i64>,
+    weight: Option<&Tensor<T>>,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    let (loss, total) = class_losses(input, target, weight, T::zero())?;
+    Ok(reduce_weighted(loss, total, reduction))
+}
+
+// `nll` of the log-softmax of the logits `input` [N, C], with `label_smoothing`
+// between 0 and 1 mixing a uniform distribution into the targets
+#[track_caller]
+pub fn cross_entropy<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<i64>,
+    weight: Option<&Tensor<T>>,
+    label_smoothing: T,
+    reduction: Reduction,
+) -> Tensor<T> {
+    error::unwrap(try_cross_entropy(
+        input,
+        target,
+        weight,
+        label_smoothing,
+        reduction,
+    ))
+}
+
+pub fn try_cross_entropy<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<i64>,
+    weight: Option<&Tensor<T>>,
+    label_smoothing: T,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    let log_probs = input.try_log_softmax(1)?;
+    let (loss, total) = class_losses(&log_probs, target, weight, label_smoothing)?;
+    Ok(reduce_weighted(loss, total, reduction))
+}
+
+// Kullback-Leibler divergence of the distribution `target` from the log-probabilities
+// `input`, target * (ln(target) - input). Zero targets contribute nothing.
+#[track_caller]
+pub fn kl_div<T: Float>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction) -> Tensor<T> {
+    error::unwrap(try_kl_div(input, target, reduction))
+}
+
+pub fn try_kl_div<T: Float>(
+    input: &Tensor<T>,
+    target: &Tensor<T>,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    check_shapes(input, target)?;
+    // ln(1) stands in for ln(0), the product with the zero target is zero either way
+    let positive = target.gt(&Tensor::scalar(T::zero()));
+    let safe = Tensor::where_(&positive, target, &target.ones_like());
+    Ok(reduce(target * (safe.ln() - input), reduction))
+}
+
+// ============================================================================
+// Embeddings
+
+// Pulls the rows of `x1` and `x2` [N, D] together where `target` [N] is 1 and pushes
+// them apart where it is -1: 1 - cos(x1, x2), or max(0, cos(x1, x2) - margin)
+#[track_caller]
+pub fn cosine_embedding<T: Float>(
+    x1: &Tensor<T>,
+    x2: &Tensor<T>,
+    target: &Tensor<T>,
+    margin: T,
+    reduction: Reduction,
+) -> Tensor<T> {
+    error::unwrap(try_cosine_embedding(x1, x2, target, margin, reduction))
+}
+
+pub fn try_cosine_embedding<T: Float>(
+    x1: &Tensor<T>,
+    x2: &Tensor<T>,
+    target: &Tensor<T>,
+    margin: T,
+    reduction: Reduction,
+) -> Result<Tensor<T>> {
+    check_shapes(x1, x2)?;
+    if x1.ndim() != 2 {
+        return Err(TensorError::RankMismatch {
+            expected: 2,
+            shape: x1.shape().to_vec(),
+        });
+    }
+    if target.shape() != [x1.shape()[0]] {
+        return Err(TensorError::ShapeMismatch {
+            lhs: x1.shape().to_vec(),
+            rhs: target.shape().to_vec(),
+        });
+    }
+    // The epsilon keeps the gradient finite for zero rows
+    let eps = T::from_f64(1e-12);
+    let dot = (x1 * x2).try_sum(1, false)?;
+    let norm1 = (x1 * x1).try_sum(1, false)? + eps;
+    let norm2 = (x2 * x2).try_sum(1, false)? + eps;
+    let cos = dot / (norm1 * norm2).sqrt();
+    let similar = target.gt(&Tensor::scalar(T::zero()));
+    let pull = -&cos + T::one();
+    let push = (cos - margin).relu();
+    Ok(reduce(Tensor::where_(&similar, &pull, &push), reduction))
+}
@@ -0,0 +1,138 @@
+mod activation;
+mod dropout;
+mod linear;
+mod sequential;
+
+pub use activation::{
+    Elu, Gelu, LeakyRelu, LogSoftmax, Relu, Sigmoid, Silu, Softmax, Softplus, Tanh,
+};
+pub use dropout::Dropout;
+pub use linear::Linear;
+pub use sequential::Sequential;
+
+use crate::dtype::Float;
+use crate::tensor::Tensor;
+
+// A layer or a model built from layers. Parameters are the trainable tensors of a
+// module and its submodules, named by their path like `0.weight` for the weight of
+// the first layer of a `Sequential`.
+pub trait Module<T: Float = f64> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>;
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        Vec::new()
+    }
+
+    fn named_parameters_mut(&mut self) -> Vec<(String, &mut Tensor<T>)> {
+        Vec::new()
+    }
+
+    // Direct submodules with their names
+    fn named_children(&self) -> Vec<(String, &dyn Module<T>)> {
+        Vec::new()
+    }
+
+    // Switches between training and evaluation behavior, e.g. of `Dropout`. Containers
+    // pass the mode on to their submodules.
+    fn set_training(&mut self, _training: bool) {}
+
+    fn train(&mut self) {
+        self.set_training(true);
+    }
+
+    fn eval(&mut self) {
+        self.set_training(false);
+    }
+
+    fn parameters(&self) -> Vec<&Tensor<T>> {
+        self.named_parameters()
+            .into_iter()
+            .map(|(_, param)| param)
+            .collect()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>> {
+        self.named_parameters_mut()
+            .into_iter()
+            .map(|(_, param)| param)
+            .collect()
+    }
+
+    // Number of trainable elements
+    fn num_parameters(&self) -> usize {
+        self.parameters().iter().map(|param| param.numel()).sum()
+    }
+
+    // Clears the accumulated gradient of every parameter
+    fn zero_grad(&self) {
+        for param in self.parameters() {
+            param.zero_grad();
+        }
+    }
+}
+
+// Prepends `prefix.` to the parameter names of a submodule
+fn prefixed<P>(prefix: &str, params: Vec<(String, P)>) -> impl Iterator<Item = (String, P)> {
+    let prefix = prefix.to_string();
+    params
+        .into_iter()
+        .map(move |(name, param)| (format!("{}.{}", prefix, name), param))
+}
+
+// Names the items of `params` `prefix.0`, `prefix.1`, ...
+fn numbered<P>(
+    prefix: &str,
+    params: impl IntoIterator<Item = P>,
+) -> impl Iterator<Item = (String, P)> {
+    let prefix = prefix.to_string();
+    params
+        .into_iter()
+        .enumerate()
+        .map(move |(i, param)| (format!("{}.{}", prefix, i), param))
+}
+
+// A stack of fully connected layers with a sigmoid activation after each one.
+// Layer `i` maps an input of shape [in, batch] to [out, batch] using a weight
+// of shape [out, in] and a bias of shape [out, 1] broadcast over the batch.
+pub struct NeuralNetwork<T = f64> {
+    pub weights: Vec<Tensor<T>>,
+    pub biases: Vec<Tensor<T>>,
+}
+
+impl<T: Float> NeuralNetwork<T> {
+    pub fn new(weights: Vec<Tensor<T>>, biases: Vec<Tensor<T>>) -> NeuralNetwork<T> {
+        assert_eq!(
+            weights.len(),
+            biases.len(),
+            "Every layer needs exactly one weight and one bias."
+        );
+        NeuralNetwork { weights, biases }
+    }
+}
+
+impl<T: Float> Module<T> for NeuralNetwork<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        let mut current_output = input.clone();
+
+        for (weight, bias) in self.weights.iter().zip(self.biases.iter()) {
+            // Apply linear transformation: weight * input + bias
+            current_output = weight.matmul(&current_output) + bias;
+            // Apply activation function (e.g., sigmoid)
+            current_output = current_output.sigmoid();
+        }
+        current_output
+    }
+
+    // Weights and biases of every layer, so they can be marked for autograd and updated
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        numbered("weights", &self.weights)
+            .chain(numbered("biases", &self.biases))
+            .collect()
+    }
+
+    fn named_parameters_mut(&mut self) -> Vec<(String, &mut Tensor<T>)> {
+        numbered("weights", &mut self.weights)
+            .chain(numbered("biases", &mut self.biases))
+            .collect()
+    }
+}
@@ -0,0 +1,77 @@
+use super::Module;
+use crate::dtype::Float;
+use crate::tensor::Tensor;
+
+// Layers applying an element-wise activation without parameters, see the methods
+// of the same name on `Tensor`
+macro_rules! activation {
+    ($($name:ident => $method:ident),* $(,)?) => {
+        $(
+            #[derive(Debug, Clone, Copy, Default)]
+            pub struct $name;
+
+            impl<T: Float> Module<T> for $name {
+                fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+                    input.$method()
+                }
+            }
+        )*
+    };
+}
+
+activation! {
+    Sigmoid => sigmoid,
+    Tanh => tanh,
+    Relu => relu,
+    Gelu => gelu,
+    Silu => silu,
+    Softplus => softplus,
+}
+
+// `x` for positive inputs and `negative_slope * x` otherwise
+#[derive(Debug, Clone, Copy)]
+pub struct LeakyRelu<T = f64> {
+    pub negative_slope: T,
+}
+
+impl<T: Float> Module<T> for LeakyRelu<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        input.leaky_relu(self.negative_slope)
+    }
+}
+
+// `x` for positive inputs and `alpha * (exp(x) - 1)` otherwise
+#[derive(Debug, Clone, Copy)]
+pub struct Elu<T = f64> {
+    pub alpha: T,
+}
+
+impl<T: Float> Module<T> for Elu<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        input.elu(self.alpha)
+    }
+}
+
+// Normalizes `axis` into probabilities
+#[derive(Debug, Clone, Copy)]
+pub struct Softmax {
+    pub axis: usize,
+}
+
+impl<T: Float> Module<T> for Softmax {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        input.softmax(self.axis)
+    }
+}
+
+// Logarithm of `Softmax`, computed without overflow
+#[derive(Debug, Clone, Copy)]
+pub struct LogSoftmax {
+    pub axis: usize,
+}
+
+impl<T: Float> Module<T> for LogSoftmax {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        input.log_softmax(self.axis)
+    }
+}
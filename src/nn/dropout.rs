@@ -0,0 +1,52 @@
+use std::cell::RefCell;
+
+use super::Module;
+use crate::dtype::Float;
+use crate::random::Rng;
+use crate::tensor::Tensor;
+
+// Zeroes every element with probability `p` during training and scales the others
+// by 1 / (1 - p), so the expected output equals the input. In evaluation mode the
+// input passes through unchanged.
+pub struct Dropout {
+    p: f64,
+    training: bool,
+    // Forward only borrows the layer, so the generator is advanced through a cell
+    rng: RefCell<Rng>,
+}
+
+impl Dropout {
+    // The layer gets its own generator seeded from `rng`
+    pub fn new(p: f64, rng: &mut Rng) -> Dropout {
+        assert!(
+            (0.0..1.0).contains(&p),
+            "Dropout probability must lie in [0, 1), got {}.",
+            p
+        );
+        Dropout {
+            p,
+            training: true,
+            rng: RefCell::new(Rng::new(rng.next_u64())),
+        }
+    }
+
+    pub fn is_training(&self) -> bool {
+        self.training
+    }
+}
+
+impl<T: Float> Module<T> for Dropout {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        if !self.training || self.p == 0.0 {
+            return input.clone();
+        }
+        let (p, scale) = (T::from_f64(self.p), T::from_f64(1.0 / (1.0 - self.p)));
+        let uniform = Tensor::<T>::rand(input.shape().to_vec(), &mut self.rng.borrow_mut());
+        let mask = uniform.map(|u| if u < p { T::zero() } else { scale });
+        input * &mask
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.training = training;
+    }
+}
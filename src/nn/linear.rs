@@ -0,0 +1,80 @@
+use super::Module;
+use crate::dtype::Float;
+use crate::init::Init;
+use crate::random::Rng;
+use crate::tensor::Tensor;
+
+// Fully connected layer y = x W^T + b. The input has the features in its last
+// dimension, [batch, in] or just [in], and the output has `out` features there.
+// The weight has shape [out, in] and the bias [out].
+pub struct Linear<T = f64> {
+    pub weight: Tensor<T>,
+    pub bias: Option<Tensor<T>>,
+}
+
+impl<T: Float> Linear<T> {
+    // Layer with a Xavier uniform weight and a zero bias, marked for autograd
+    pub fn new(in_features: usize, out_features: usize, rng: &mut Rng) -> Linear<T> {
+        let weight = Init::XavierUniform { gain: 1.0 };
+        Linear::with_init(in_features, out_features, weight, Some(Init::zeros()), rng)
+    }
+
+    // Layer with parameters drawn from the given schemes, without a bias if `bias` is
+    // `None`. Use a gain suited to the activation that follows, see `Nonlinearity`.
+    pub fn with_init(
+        in_features: usize,
+        out_features: usize,
+        weight: Init,
+        bias: Option<Init>,
+        rng: &mut Rng,
+    ) -> Linear<T> {
+        let weight = weight.tensor(vec![out_features, in_features], rng);
+        let bias = bias.map(|bias| bias.tensor(vec![out_features], rng));
+        Linear::from_tensors(weight, bias)
+    }
+
+    // Layer with the given parameters, which are marked for autograd
+    pub fn from_tensors(mut weight: Tensor<T>, mut bias: Option<Tensor<T>>) -> Linear<T> {
+        assert_eq!(weight.ndim(), 2, "The weight must be a matrix.");
+        if let Some(bias) = &mut bias {
+            assert_eq!(
+                bias.shape(),
+                &weight.shape()[..1],
+                "The bias needs one element per output feature."
+            );
+            bias.set_requires_grad(true);
+        }
+        weight.set_requires_grad(true);
+        Linear { weight, bias }
+    }
+
+    pub fn in_features(&self) -> usize {
+        self.weight.shape()[1]
+    }
+
+    pub fn out_features(&self) -> usize {
+        self.weight.shape()[0]
+    }
+}
+
+impl<T: Float> Module<T> for Linear<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        let output = input.matmul(&self.weight.t());
+        match &self.bias {
+            Some(bias) => output + bias,
+            None => output,
+        }
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        let mut params = vec![("weight".to_string(), &self.weight)];
+        params.extend(self.bias.iter().map(|bias| ("bias".to_string(), bias)));
+        params
+    }
+
+    fn named_parameters_mut(&mut self) -> Vec<(String, &mut Tensor<T>)> {
+        let mut params = vec![("weight".to_string(), &mut self.weight)];
+        params.extend(self.bias.iter_mut().map(|bias| ("bias".to_string(), bias)));
+        params
+    }
+}
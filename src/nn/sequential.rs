@@ -0,0 +1,81 @@
+use super::{prefixed, Module};
+use crate::dtype::Float;
+use crate::tensor::Tensor;
+
+// Runs its layers one after the other, feeding the output of each into the next.
+// Layers are named by their position.
+pub struct Sequential<T = f64> {
+    layers: Vec<Box<dyn Module<T>>>,
+}
+
+impl<T: Float> Sequential<T> {
+    pub fn new() -> Sequential<T> {
+        Sequential { layers: Vec::new() }
+    }
+
+    // Appends a layer, for building a model in one expression
+    pub fn with(mut self, layer: impl Module<T> + 'static) -> Sequential<T> {
+        self.push(layer);
+        self
+    }
+
+    pub fn push(&mut self, layer: impl Module<T> + 'static) {
+        self.layers.push(Box::new(layer));
+    }
+
+    pub fn layers(&self) -> &[Box<dyn Module<T>>] {
+        &self.layers
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+impl<T: Float> Default for Sequential<T> {
+    fn default() -> Self {
+        Sequential::new()
+    }
+}
+
+impl<T: Float> Module<T> for Sequential<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        self.layers
+            .iter()
+            .fold(input.clone(), |output, layer| layer.forward(&output))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        self.layers
+            .iter()
+            .enumerate()
+            .flat_map(|(i, layer)| prefixed(&i.to_string(), layer.named_parameters()))
+            .collect()
+    }
+
+    fn named_parameters_mut(&mut self) -> Vec<(String, &mut Tensor<T>)> {
+        self.layers
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(i, layer)| prefixed(&i.to_string(), layer.named_parameters_mut()))
+            .collect()
+    }
+
+    fn named_children(&self) -> Vec<(String, &dyn Module<T>)> {
+        self.layers
+            .iter()
+            .enumerate()
+            .map(|(i, layer)| (i.to_string(), layer.as_ref()))
+            .collect()
+    }
+
+    fn set_training(&mut self, training: bool) {
+        for layer in &mut self.layers {
+            layer.set_training(training);
+        }
+    }
+}
@@ -0,0 +1,330 @@
+mod adagrad;
+mod adam;
+mod rmsprop;
+mod scheduler;
+mod sgd;
+
+pub use adagrad::Adagrad;
+pub use adam::Adam;
+pub use rmsprop::RmsProp;
+pub use scheduler::{
+    CosineAnnealingWarmRestarts, ExponentialLr, LinearWarmup, OneCycle, OneCycleConfig,
+    PlateauMode, ReduceOnPlateau, Scheduler, StepLr,
+};
+pub use sgd::Sgd;
+
+use std::collections::BTreeMap;
+
+use crate::dtype::Float;
+use crate::error::{Result, TensorError};
+use crate::nn::Module;
+use crate::tensor::Tensor;
+
+// An update rule for trainable parameters. Parameters are identified by their names,
+// which key the internal state like momentum buffers, so the same names have to be
+// passed on every step. Updates are not recorded on the graph.
+pub trait Optimizer<T: Float = f64> {
+    // Updates every parameter from its accumulated gradient, parameters without a
+    // gradient are left unchanged. Gradients are not cleared.
+    fn step_parameters(&mut self, params: Vec<(String, &mut Tensor<T>)>);
+
+    // The learning rate and buffers shared by every optimizer
+    fn core(&self) -> &OptimizerCore<T>;
+
+    fn core_mut(&mut self) -> &mut OptimizerCore<T>;
+
+    // Updates the parameters of `module`
+    fn step(&mut self, module: &mut dyn Module<T>) {
+        self.step_parameters(module.named_parameters_mut());
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.core().learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.core_mut().learning_rate = learning_rate;
+    }
+
+    // Snapshot of the internal state, to resume training with `load_state`
+    fn state(&self) -> OptimizerState<T> {
+        OptimizerState {
+            learning_rate: self.core().learning_rate,
+            buffers: self.core().buffers.map.clone(),
+        }
+    }
+
+    // Replaces the internal state with `state` after checking that its buffers belong
+    // to the given parameters. On error the optimizer is left unchanged.
+    fn load_state_parameters(
+        &mut self,
+        state: OptimizerState<T>,
+        params: Vec<(String, &Tensor<T>)>,
+    ) -> Result<()> {
+        for (key, buffer) in &state.buffers {
+            // Parameter names may contain dots, buffer kinds do not
+            let (name, kind) = key.rsplit_once('.').unwrap_or(("", key));
+            let param = params
+                .iter()
+                .find(|(param_name, _)| param_name == name)
+                .ok_or_else(|| TensorError::UnknownParameter { name: key.clone() })?;
+            // Step counters are 0-D, every other buffer matches its parameter
+            let expected = if kind == STEP {
+                &[][..]
+            } else {
+                param.1.shape()
+            };
+            if buffer.shape() != expected {
+                return Err(TensorError::ShapeMismatch {
+                    lhs: expected.to_vec(),
+                    rhs: buffer.shape().to_vec(),
+                });
+            }
+        }
+        let core = self.core_mut();
+        core.learning_rate = state.learning_rate;
+        core.buffers.map = state.buffers;
+        Ok(())
+    }
+
+    // `load_state_parameters` for the parameters of `module`
+    fn load_state(&mut self, state: OptimizerState<T>, module: &dyn Module<T>) -> Result<()> {
+        self.load_state_parameters(state, module.named_parameters())
+    }
+}
+
+// State every optimizer keeps: the learning rate, which schedulers adjust, and the
+// per-parameter buffers that are saved with `Optimizer::state`
+#[derive(Clone)]
+pub struct OptimizerCore<T = f64> {
+    learning_rate: f64,
+    buffers: Buffers<T>,
+}
+
+impl<T: Float> OptimizerCore<T> {
+    pub fn new(learning_rate: f64) -> OptimizerCore<T> {
+        OptimizerCore {
+            learning_rate,
+            buffers: Buffers {
+                map: BTreeMap::new(),
+            },
+        }
+    }
+}
+
+// Internal state of an optimizer. Buffers are keyed by the parameter name and the
+// kind of buffer, e.g. `0.weight.exp_avg`.
+#[derive(Clone)]
+pub struct OptimizerState<T = f64> {
+    pub learning_rate: f64,
+    pub buffers: BTreeMap<String, Tensor<T>>,
+}
+
+impl<T: Float> OptimizerState<T> {
+    // Line-based text form, one line per value:
+    // learning_rate 0.001
+    // buffer <key> <ndim> <dims...> <values...>
+    // Values are written with enough digits to be read back exactly.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("learning_rate {:?}\n", self.learning_rate);
+        for (key, buffer) in &self.buffers {
+            text += &format!("buffer {} {}", key, buffer.ndim());
+            for dim in buffer.shape() {
+                text += &format!(" {}", dim);
+            }
+            for x in buffer.iter() {
+                text += &format!(" {:?}", x.to_f64());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<OptimizerState<T>> {
+        let mut state = OptimizerState {
+            learning_rate: 0.0,
+            buffers: BTreeMap::new(),
+        };
+        let mut has_learning_rate = false;
+        for (i, line) in text.lines().enumerate() {
+            let invalid = |reason| TensorError::InvalidState {
+                line: i + 1,
+                reason,
+            };
+            let mut fields = line.split_whitespace();
+            match fields.next() {
+                None => {}
+                Some("learning_rate") => {
+                    state.learning_rate = parse(fields.next(), i + 1)?;
+                    has_learning_rate = true;
+                }
+                Some("buffer") => {
+                    let key = fields.next().ok_or(invalid("missing buffer key"))?;
+                    let ndim: usize = parse(fields.next(), i + 1)?;
+                    let shape = (0..ndim)
+                        .map(|_| parse(fields.next(), i + 1))
+                        .collect::<Result<Vec<usize>>>()?;
+                    let data = fields
+                        .map(|field| parse(Some(field), i + 1).map(T::from_f64))
+                        .collect::<Result<Vec<T>>>()?;
+                    let buffer = Tensor::try_from_data(shape, data)
+                        .map_err(|_| invalid("buffer length does not match its shape"))?;
+                    state.buffers.insert(key.to_string(), buffer);
+                }
+                Some(_) => return Err(invalid("unknown entry")),
+            }
+        }
+        if !has_learning_rate {
+            // Reported past the last line, where the entry would have been expected
+            return Err(TensorError::InvalidState {
+                line: text.lines().count() + 1,
+                reason: "missing learning_rate",
+            });
+        }
+        Ok(state)
+    }
+}
+
+// Parses one field of line `line` of a serialized state
+fn parse<F: std::str::FromStr>(field: Option<&str>, line: usize) -> Result<F> {
+    field
+        .and_then(|field| field.parse().ok())
+        .ok_or(TensorError::InvalidState {
+            line,
+            reason: "missing or malformed number",
+        })
+}
+
+// Kind of the per-parameter step counters, the only buffers that are not shaped like
+// their parameter
+const STEP: &str = "step";
+
+// Per-parameter buffers of an optimizer, like the running averages of Adam
+#[derive(Clone)]
+struct Buffers<T> {
+    map: BTreeMap<String, Tensor<T>>,
+}
+
+impl<T: Float> Buffers<T> {
+    // Removes the `kind` buffer of parameter `name`, zeros of length `len` if there is
+    // none yet. Give it back with `put` after updating it.
+    fn take(&mut self, name: &str, kind: &str, len: usize) -> Vec<T> {
+        match self.map.remove(&format!("{}.{}", name, kind)) {
+            Some(buffer) => {
+                assert_eq!(
+                    buffer.numel(),
+                    len,
+                    "Optimizer state for {} does not match the parameter.",
+                    name
+                );
+                buffer.to_vec()
+            }
+            None => vec![T::zero(); len],
+        }
+    }
+
+    fn put(&mut self, name: &str, kind: &str, shape: &[usize], data: Vec<T>) {
+        self.map.insert(
+            format!("{}.{}", name, kind),
+            Tensor::from_data(shape.to_vec(), data),
+        );
+    }
+}
+
+// Gradient of `param` as a vector, `None` if it has none
+fn gradient<T: Float>(param: &Tensor<T>) -> Option<Vec<T>> {
+    param.grad().map(|grad| grad.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::Linear;
+    use crate::random::Rng;
+
+    // A Linear(3, 2) layer with gradients and an Adam optimizer that has stepped once
+    fn trained() -> (Linear, Adam) {
+        let mut rng = Rng::new(0);
+        let mut layer = Linear::new(3, 2, &mut rng);
+        let input = Tensor::rand(vec![4, 3], &mut rng);
+        layer.forward(&input).sum_all().backward();
+        let mut optimizer = Adam::new(0.1);
+        optimizer.step(&mut layer);
+        (layer, optimizer)
+    }
+
+    #[test]
+    fn load_state_accepts_own_state() {
+        let (mut layer, optimizer) = trained();
+        let text = optimizer.state().to_text();
+        let mut resumed = Adam::new(0.5);
+        let state = OptimizerState::from_text(&text).unwrap();
+        resumed.load_state(state, &layer).unwrap();
+        assert_eq!(resumed.learning_rate(), 0.1);
+        resumed.step(&mut layer);
+    }
+
+    #[test]
+    fn load_state_rejects_0d_buffer() {
+        let (mut layer, optimizer) = trained();
+        let mut state = optimizer.state();
+        state
+            .buffers
+            .insert("weight.exp_avg".to_string(), Tensor::scalar(1.0));
+        let mut fresh = Adam::new(0.5);
+        assert_eq!(
+            fresh.load_state(state, &layer),
+            Err(TensorError::ShapeMismatch {
+                lhs: vec![2, 3],
+                rhs: vec![]
+            })
+        );
+        // Rejected state is not kept, so stepping still works
+        assert_eq!(fresh.learning_rate(), 0.5);
+        fresh.step(&mut layer);
+    }
+
+    #[test]
+    fn load_state_rejects_misshapen_step_counter() {
+        let (layer, optimizer) = trained();
+        let mut state = optimizer.state();
+        state
+            .buffers
+            .insert("bias.step".to_string(), Tensor::zeros(vec![2]));
+        assert!(matches!(
+            Adam::new(0.1).load_state(state, &layer),
+            Err(TensorError::ShapeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn from_text_requires_learning_rate() {
+        let text = "buffer weight.exp_avg 1 2 0.5 0.25\n";
+        assert_eq!(
+            OptimizerState::<f64>::from_text(text).err(),
+            Some(TensorError::InvalidState {
+                line: 2,
+                reason: "missing learning_rate"
+            })
+        );
+        let text = "learning_rate 0.01\n".to_string() + text;
+        let state = OptimizerState::<f64>::from_text(&text).unwrap();
+        assert_eq!(state.learning_rate, 0.01);
+        assert_eq!(state.buffers["weight.exp_avg"].to_vec(), vec![0.5, 0.25]);
+    }
+
+    #[test]
+    fn load_state_rejects_unknown_parameter() {
+        let (layer, optimizer) = trained();
+        let mut state = optimizer.state();
+        state
+            .buffers
+            .insert("0.weight.exp_avg".to_string(), Tensor::zeros(vec![2, 3]));
+        assert_eq!(
+            Adam::new(0.1).load_state(state, &layer),
+            Err(TensorError::UnknownParameter {
+                name: "0.weight.exp_avg".to_string()
+            })
+        );
+    }
+}
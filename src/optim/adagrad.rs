@@ -0,0 +1,65 @@
+use super::{gradient, Optimizer, OptimizerCore};
+use crate::dtype::Float;
+use crate::tensor::Tensor;
+
+// Adagrad divides the learning rate of every element by the root of the sum of all
+// its squared gradients so far:
+// s += g^2
+// p -= lr * g / (sqrt(s) + eps)
+#[derive(Clone)]
+pub struct Adagrad<T = f64> {
+    eps: f64,
+    weight_decay: f64,
+    core: OptimizerCore<T>,
+}
+
+impl<T: Float> Adagrad<T> {
+    pub fn new(learning_rate: f64) -> Adagrad<T> {
+        Adagrad {
+            eps: 1e-10,
+            weight_decay: 0.0,
+            core: OptimizerCore::new(learning_rate),
+        }
+    }
+
+    pub fn eps(mut self, eps: f64) -> Adagrad<T> {
+        self.eps = eps;
+        self
+    }
+
+    pub fn weight_decay(mut self, weight_decay: f64) -> Adagrad<T> {
+        self.weight_decay = weight_decay;
+        self
+    }
+}
+
+impl<T: Float> Optimizer<T> for Adagrad<T> {
+    fn step_parameters(&mut self, params: Vec<(String, &mut Tensor<T>)>) {
+        let lr = T::from_f64(self.core.learning_rate);
+        let eps = T::from_f64(self.eps);
+        let weight_decay = T::from_f64(self.weight_decay);
+        for (name, param) in params {
+            let grad = match gradient(param) {
+                Some(grad) => grad,
+                None => continue,
+            };
+            let shape = param.shape().to_vec();
+            let p = param.data_mut();
+            let mut sum = self.core.buffers.take(&name, "sum", p.len());
+            for i in 0..p.len() {
+                let g = grad[i] + weight_decay * p[i];
+                sum[i] = sum[i] + g * g;
+                p[i] = p[i] - lr * g / (sum[i].sqrt() + eps);
+            }
+            self.core.buffers.put(&name, "sum", &shape, sum);
+        }
+    }
+
+    fn core(&self) -> &OptimizerCore<T> {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut OptimizerCore<T> {
+        &mut self.core
+    }
+}
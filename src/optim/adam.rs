@@ -0,0 +1,131 @@
+use super::{gradient, Optimizer, OptimizerCore, STEP};
+use crate::dtype::Float;
+use crate::tensor::Tensor;
+
+// Adam with bias-corrected running averages of the gradient and its square:
+// m = beta1 * m + (1 - beta1) * g
+// v = beta2 * v + (1 - beta2) * g^2
+// p -= lr * m_hat / (sqrt(v_hat) + eps)
+// Weight decay is added to the gradient like L2 regularization, or applied directly
+// to the parameters for AdamW. With `amsgrad` the largest `v` seen so far is used.
+#[derive(Clone)]
+pub struct Adam<T = f64> {
+    beta1: f64,
+    beta2: f64,
+    eps: f64,
+    weight_decay: f64,
+    decoupled: bool,
+    amsgrad: bool,
+    core: OptimizerCore<T>,
+}
+
+impl<T: Float> Adam<T> {
+    pub fn new(learning_rate: f64) -> Adam<T> {
+        Adam {
+            beta1: 0.9,
+            beta2: 0.999,
+            eps: 1e-8,
+            weight_decay: 0.0,
+            decoupled: false,
+            amsgrad: false,
+            core: OptimizerCore::new(learning_rate),
+        }
+    }
+
+    // AdamW: the weight decay shrinks the parameters by `lr * weight_decay` each step
+    // instead of being added to the gradient, the default decay is 0.01
+    pub fn adamw(learning_rate: f64) -> Adam<T> {
+        Adam {
+            weight_decay: 0.01,
+            decoupled: true,
+            ..Adam::new(learning_rate)
+        }
+    }
+
+    pub fn betas(mut self, beta1: f64, beta2: f64) -> Adam<T> {
+        self.beta1 = beta1;
+        self.beta2 = beta2;
+        self
+    }
+
+    pub fn eps(mut self, eps: f64) -> Adam<T> {
+        self.eps = eps;
+        self
+    }
+
+    pub fn weight_decay(mut self, weight_decay: f64) -> Adam<T> {
+        self.weight_decay = weight_decay;
+        self
+    }
+
+    pub fn amsgrad(mut self, amsgrad: bool) -> Adam<T> {
+        self.amsgrad = amsgrad;
+        self
+    }
+}
+
+impl<T: Float> Optimizer<T> for Adam<T> {
+    fn step_parameters(&mut self, params: Vec<(String, &mut Tensor<T>)>) {
+        let lr = T::from_f64(self.core.learning_rate);
+        let (beta1, beta2) = (T::from_f64(self.beta1), T::from_f64(self.beta2));
+        let eps = T::from_f64(self.eps);
+        let weight_decay = T::from_f64(self.weight_decay);
+        let one = T::one();
+        for (name, param) in params {
+            let grad = match gradient(param) {
+                Some(grad) => grad,
+                None => continue,
+            };
+            // Bias correction counts the steps that updated this parameter
+            let step = self.core.buffers.take(&name, STEP, 1)[0] + one;
+            self.core.buffers.put(&name, STEP, &[], vec![step]);
+            let t = step.to_f64() as i32;
+            let correction1 = T::from_f64(1.0 - self.beta1.powi(t));
+            let correction2 = T::from_f64(1.0 - self.beta2.powi(t)).sqrt();
+            let shape = param.shape().to_vec();
+            let p = param.data_mut();
+            let mut m = self.core.buffers.take(&name, "exp_avg", p.len());
+            let mut v = self.core.buffers.take(&name, "exp_avg_sq", p.len());
+            let mut v_max = if self.amsgrad {
+                self.core.buffers.take(&name, "max_exp_avg_sq", p.len())
+            } else {
+                Vec::new()
+            };
+            for i in 0..p.len() {
+                let mut g = grad[i];
+                if self.decoupled {
+                    p[i] = p[i] * (one - lr * weight_decay);
+                } else {
+                    g = g + weight_decay * p[i];
+                }
+                m[i] = beta1 * m[i] + (one - beta1) * g;
+                v[i] = beta2 * v[i] + (one - beta2) * g * g;
+                let second = if self.amsgrad {
+                    if v[i] > v_max[i] {
+                        v_max[i] = v[i];
+                    }
+                    v_max[i]
+                } else {
+                    v[i]
+                };
+                let denom = second.sqrt() / correction2 + eps;
+                p[i] = p[i] - lr * m[i] / correction1 / denom;
+            }
+            self.core.buffers.put(&name, "exp_avg", &shape, m);
+            self.core.buffers.put(&name, "exp_avg_sq", &shape, v);
+            if self.amsgrad {
+                self.core
+                    .buffers
+                    .put(&name, "max_exp_avg_sq", &shape, v_max);
+            }
+        }
+    }
+
+    fn core(&self) -> &OptimizerCore<T> {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut OptimizerCore<T> {
+        &mut self.core
+    }
+}
@@ -0,0 +1,119 @@
+use super::{gradient, Optimizer, OptimizerCore};
+use crate::dtype::Float;
+use crate::tensor::Tensor;
+
+// RMSprop scales the gradient by a running average of its square:
+// v = alpha * v + (1 - alpha) * g^2
+// p -= lr * g / (sqrt(v) + eps)
+// The centered variant subtracts the squared running mean of the gradient from `v`,
+// with momentum the scaled gradient is accumulated in a buffer first.
+#[derive(Clone)]
+pub struct RmsProp<T = f64> {
+    alpha: f64,
+    eps: f64,
+    weight_decay: f64,
+    momentum: f64,
+    centered: bool,
+    core: OptimizerCore<T>,
+}
+
+impl<T: Float> RmsProp<T> {
+    pub fn new(learning_rate: f64) -> RmsProp<T> {
+        RmsProp {
+            alpha: 0.99,
+            eps: 1e-8,
+            weight_decay: 0.0,
+            momentum: 0.0,
+            centered: false,
+            core: OptimizerCore::new(learning_rate),
+        }
+    }
+
+    // Smoothing constant of the running averages
+    pub fn alpha(mut self, alpha: f64) -> RmsProp<T> {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn eps(mut self, eps: f64) -> RmsProp<T> {
+        self.eps = eps;
+        self
+    }
+
+    pub fn weight_decay(mut self, weight_decay: f64) -> RmsProp<T> {
+        self.weight_decay = weight_decay;
+        self
+    }
+
+    pub fn momentum(mut self, momentum: f64) -> RmsProp<T> {
+        self.momentum = momentum;
+        self
+    }
+
+    pub fn centered(mut self, centered: bool) -> RmsProp<T> {
+        self.centered = centered;
+        self
+    }
+}
+
+impl<T: Float> Optimizer<T> for RmsProp<T> {
+    fn step_parameters(&mut self, params: Vec<(String, &mut Tensor<T>)>) {
+        let lr = T::from_f64(self.core.learning_rate);
+        let alpha = T::from_f64(self.alpha);
+        let eps = T::from_f64(self.eps);
+        let weight_decay = T::from_f64(self.weight_decay);
+        let momentum = T::from_f64(self.momentum);
+        let one = T::one();
+        for (name, param) in params {
+            let grad = match gradient(param) {
+                Some(grad) => grad,
+                None => continue,
+            };
+            let shape = param.shape().to_vec();
+            let p = param.data_mut();
+            let mut v = self.core.buffers.take(&name, "square_avg", p.len());
+            let mut mean = if self.centered {
+                self.core.buffers.take(&name, "grad_avg", p.len())
+            } else {
+                Vec::new()
+            };
+            let mut buf = if self.momentum != 0.0 {
+                self.core.buffers.take(&name, "momentum", p.len())
+            } else {
+                Vec::new()
+            };
+            for i in 0..p.len() {
+                let g = grad[i] + weight_decay * p[i];
+                v[i] = alpha * v[i] + (one - alpha) * g * g;
+                let variance = if self.centered {
+                    mean[i] = alpha * mean[i] + (one - alpha) * g;
+                    v[i] - mean[i] * mean[i]
+                } else {
+                    v[i]
+                };
+                let update = g / (variance.sqrt() + eps);
+                if self.momentum != 0.0 {
+                    buf[i] = momentum * buf[i] + update;
+                    p[i] = p[i] - lr * buf[i];
+                } else {
+                    p[i] = p[i] - lr * update;
+                }
+            }
+            self.core.buffers.put(&name, "square_avg", &shape, v);
+            if self.centered {
+                self.core.buffers.put(&name, "grad_avg", &shape, mean);
+            }
+            if self.momentum != 0.0 {
+                self.core.buffers.put(&name, "momentum", &shape, buf);
+            }
+        }
+    }
+
+    fn core(&self) -> &OptimizerCore<T> {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut OptimizerCore<T> {
+        &mut self.core
+    }
+}
@@ -0,0 +1,367 @@
+use std::f64::consts::PI;
+
+use super::Optimizer;
+use crate::dtype::Float;
+
+// Adjusts the learning rate of an optimizer during training. Constructors take the
+// optimizer to read the base rate from and set its starting rate, after that call
+// `step` once per epoch, or once per batch for schedules counted in batches like
+// `OneCycle`.
+pub trait Scheduler {
+    // Learning rate of the current step
+    fn get_lr(&self) -> f64;
+
+    // Moves the schedule forward by one step and returns the new learning rate
+    fn advance(&mut self) -> f64;
+
+    // Moves the schedule forward and passes the new learning rate to `optimizer`
+    fn step<T: Float>(&mut self, optimizer: &mut dyn Optimizer<T>)
+    where
+        Self: Sized,
+    {
+        optimizer.set_learning_rate(self.advance());
+    }
+}
+
+// Cosine curve from `start` at `pct = 0` to `end` at `pct = 1`
+fn cosine(start: f64, end: f64, pct: f64) -> f64 {
+    end + (start - end) * (1.0 + (PI * pct).cos()) / 2.0
+}
+
+// Multiplies the learning rate by `gamma` every `step_size` steps
+#[derive(Debug, Clone)]
+pub struct StepLr {
+    base_lr: f64,
+    step_size: u64,
+    gamma: f64,
+    steps: u64,
+}
+
+impl StepLr {
+    pub fn new<T: Float>(optimizer: &mut dyn Optimizer<T>, step_size: u64, gamma: f64) -> StepLr {
+        assert!(step_size > 0, "Step size must be positive.");
+        StepLr {
+            base_lr: optimizer.learning_rate(),
+            step_size,
+            gamma,
+            steps: 0,
+        }
+    }
+}
+
+impl Scheduler for StepLr {
+    fn get_lr(&self) -> f64 {
+        self.base_lr * self.gamma.powi((self.steps / self.step_size) as i32)
+    }
+
+    fn advance(&mut self) -> f64 {
+        self.steps += 1;
+        self.get_lr()
+    }
+}
+
+// Multiplies the learning rate by `gamma` every step
+#[derive(Debug, Clone)]
+pub struct ExponentialLr {
+    base_lr: f64,
+    gamma: f64,
+    steps: u64,
+}
+
+impl ExponentialLr {
+    pub fn new<T: Float>(optimizer: &mut dyn Optimizer<T>, gamma: f64) -> ExponentialLr {
+        ExponentialLr {
+            base_lr: optimizer.learning_rate(),
+            gamma,
+            steps: 0,
+        }
+    }
+}
+
+impl Scheduler for ExponentialLr {
+    fn get_lr(&self) -> f64 {
+        self.base_lr * self.gamma.powi(self.steps as i32)
+    }
+
+    fn advance(&mut self) -> f64 {
+        self.steps += 1;
+        self.get_lr()
+    }
+}
+
+// SGDR: follows a cosine from the base rate down to `eta_min` over `period` steps, then
+// restarts at the base rate. Every period is `period_mult` times longer than the last.
+#[derive(Debug, Clone)]
+pub struct CosineAnnealingWarmRestarts {
+    base_lr: f64,
+    eta_min: f64,
+    period: u64,
+    period_mult: u64,
+    // Steps taken since the last restart
+    position: u64,
+}
+
+impl CosineAnnealingWarmRestarts {
+    pub fn new<T: Float>(
+        optimizer: &mut dyn Optimizer<T>,
+        period: u64,
+        period_mult: u64,
+        eta_min: f64,
+    ) -> CosineAnnealingWarmRestarts {
+        assert!(
+            period > 0 && period_mult > 0,
+            "Period and its multiplier must be positive."
+        );
+        CosineAnnealingWarmRestarts {
+            base_lr: optimizer.learning_rate(),
+            eta_min,
+            period,
+            period_mult,
+            position: 0,
+        }
+    }
+}
+
+impl Scheduler for CosineAnnealingWarmRestarts {
+    fn get_lr(&self) -> f64 {
+        let pct = self.position as f64 / self.period as f64;
+        cosine(self.base_lr, self.eta_min, pct)
+    }
+
+    fn advance(&mut self) -> f64 {
+        self.position += 1;
+        if self.position >= self.period {
+            self.position -= self.period;
+            self.period *= self.period_mult;
+        }
+        self.get_lr()
+    }
+}
+
+// Raises the learning rate linearly from `start_factor` times the base rate to the base
+// rate over `warmup_steps` steps, then keeps it there
+#[derive(Debug, Clone)]
+pub struct LinearWarmup {
+    base_lr: f64,
+    start_factor: f64,
+    warmup_steps: u64,
+    steps: u64,
+}
+
+impl LinearWarmup {
+    pub fn new<T: Float>(
+        optimizer: &mut dyn Optimizer<T>,
+        start_factor: f64,
+        warmup_steps: u64,
+    ) -> LinearWarmup {
+        let warmup = LinearWarmup {
+            base_lr: optimizer.learning_rate(),
+            start_factor,
+            warmup_steps,
+            steps: 0,
+        };
+        optimizer.set_learning_rate(warmup.get_lr());
+        warmup
+    }
+}
+
+impl Scheduler for LinearWarmup {
+    fn get_lr(&self) -> f64 {
+        if self.steps >= self.warmup_steps {
+            return self.base_lr;
+        }
+        let pct = self.steps as f64 / self.warmup_steps as f64;
+        self.base_lr * (self.start_factor + (1.0 - self.start_factor) * pct)
+    }
+
+    fn advance(&mut self) -> f64 {
+        self.steps += 1;
+        self.get_lr()
+    }
+}
+
+// Shape of the 1cycle policy, see `OneCycle`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OneCycleConfig {
+    // Fraction of the steps spent raising the learning rate
+    pub pct_start: f64,
+    // The starting rate is `max_lr / div_factor`
+    pub div_factor: f64,
+    // The final rate is the starting rate divided by `final_div_factor`
+    pub final_div_factor: f64,
+}
+
+impl Default for OneCycleConfig {
+    fn default() -> Self {
+        OneCycleConfig {
+            pct_start: 0.3,
+            div_factor: 25.0,
+            final_div_factor: 1e4,
+        }
+    }
+}
+
+// The 1cycle policy: the learning rate rises along a cosine from `max_lr / div_factor`
+// to `max_lr` over the first `pct_start` of `total_steps`, then falls to
+// `max_lr / (div_factor * final_div_factor)` by the last step
+#[derive(Debug, Clone)]
+pub struct OneCycle {
+    max_lr: f64,
+    total_steps: u64,
+    config: OneCycleConfig,
+    steps: u64,
+}
+
+impl OneCycle {
+    // Cycle with the default `OneCycleConfig`, sets the starting rate of `optimizer`
+    pub fn new<T: Float>(
+        optimizer: &mut dyn Optimizer<T>,
+        max_lr: f64,
+        total_steps: u64,
+    ) -> OneCycle {
+        OneCycle::with_config(optimizer, max_lr, total_steps, OneCycleConfig::default())
+    }
+
+    pub fn with_config<T: Float>(
+        optimizer: &mut dyn Optimizer<T>,
+        max_lr: f64,
+        total_steps: u64,
+        config: OneCycleConfig,
+    ) -> OneCycle {
+        assert!(total_steps > 0, "A cycle needs at least one step.");
+        let cycle = OneCycle {
+            max_lr,
+            total_steps,
+            config,
+            steps: 0,
+        };
+        optimizer.set_learning_rate(cycle.get_lr());
+        cycle
+    }
+}
+
+impl Scheduler for OneCycle {
+    fn get_lr(&self) -> f64 {
+        let initial = self.max_lr / self.config.div_factor;
+        let last = initial / self.config.final_div_factor;
+        let end = (self.total_steps - 1) as f64;
+        let peak = (self.config.pct_start * self.total_steps as f64 - 1.0).max(0.0);
+        let step = (self.steps as f64).min(end);
+        if step < peak {
+            cosine(initial, self.max_lr, step / peak)
+        } else if peak >= end {
+            self.max_lr
+        } else {
+            cosine(self.max_lr, last, (step - peak) / (end - peak))
+        }
+    }
+
+    fn advance(&mut self) -> f64 {
+        self.steps += 1;
+        self.get_lr()
+    }
+}
+
+// Whether `ReduceOnPlateau` watches a metric that should go down, like a loss, or up,
+// like an accuracy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlateauMode {
+    Min,
+    Max,
+}
+
+// Multiplies the learning rate by `factor` once the validation metric has not improved
+// for more than `patience` steps. Unlike the other schedules every step needs the
+// latest metric, so it is stepped with `step_with_metric` instead of `Scheduler::step`.
+#[derive(Debug, Clone)]
+pub struct ReduceOnPlateau {
+    lr: f64,
+    mode: PlateauMode,
+    factor: f64,
+    patience: u64,
+    // Relative improvement over the best value needed to count as better
+    threshold: f64,
+    // Steps to wait after a reduction before counting bad steps again
+    cooldown: u64,
+    min_lr: f64,
+    best: Option<f64>,
+    bad_steps: u64,
+    cooldown_left: u64,
+}
+
+impl ReduceOnPlateau {
+    pub fn new<T: Float>(optimizer: &mut dyn Optimizer<T>, mode: PlateauMode) -> ReduceOnPlateau {
+        ReduceOnPlateau {
+            lr: optimizer.learning_rate(),
+            mode,
+            factor: 0.1,
+            patience: 10,
+            threshold: 1e-4,
+            cooldown: 0,
+            min_lr: 0.0,
+            best: None,
+            bad_steps: 0,
+            cooldown_left: 0,
+        }
+    }
+
+    pub fn factor(mut self, factor: f64) -> ReduceOnPlateau {
+        assert!(factor < 1.0, "Factor must be below one.");
+        self.factor = factor;
+        self
+    }
+
+    pub fn patience(mut self, patience: u64) -> ReduceOnPlateau {
+        self.patience = patience;
+        self
+    }
+
+    pub fn threshold(mut self, threshold: f64) -> ReduceOnPlateau {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn cooldown(mut self, cooldown: u64) -> ReduceOnPlateau {
+        self.cooldown = cooldown;
+        self
+    }
+
+    pub fn min_lr(mut self, min_lr: f64) -> ReduceOnPlateau {
+        self.min_lr = min_lr;
+        self
+    }
+
+    fn is_better(&self, metric: f64, best: f64) -> bool {
+        match self.mode {
+            PlateauMode::Min => metric < best - best.abs() * self.threshold,
+            PlateauMode::Max => metric > best + best.abs() * self.threshold,
+        }
+    }
+
+    // Learning rate of the current step
+    pub fn get_lr(&self) -> f64 {
+        self.lr
+    }
+
+    // Moves forward by one step given the latest `metric` and passes the new learning
+    // rate to `optimizer`
+    pub fn step_with_metric<T: Float>(&mut self, metric: f64, optimizer: &mut dyn Optimizer<T>) {
+        match self.best {
+            Some(best) if !self.is_better(metric, best) => self.bad_steps += 1,
+            _ => {
+                self.best = Some(metric);
+                self.bad_steps = 0;
+            }
+        }
+        if self.cooldown_left > 0 {
+            self.cooldown_left -= 1;
+            self.bad_steps = 0;
+        }
+        if self.bad_steps > self.patience {
+            self.lr = (self.lr * self.factor).max(self.min_lr);
+            self.cooldown_left = self.cooldown;
+            self.bad_steps = 0;
+        }
+        optimizer.set_learning_rate(self.lr);
+    }
+}
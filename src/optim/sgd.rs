@@ -0,0 +1,86 @@
+use super::{gradient, Optimizer, OptimizerCore};
+use crate::dtype::Float;
+use crate::tensor::Tensor;
+
+// Stochastic gradient descent with optional momentum, Nesterov momentum and L2
+// weight decay, following PyTorch:
+// g = grad + weight_decay * p
+// b = momentum * b + g
+// p -= lr * (g + momentum * b) with Nesterov, p -= lr * b otherwise
+#[derive(Clone)]
+pub struct Sgd<T = f64> {
+    momentum: f64,
+    nesterov: bool,
+    weight_decay: f64,
+    core: OptimizerCore<T>,
+}
+
+impl<T: Float> Sgd<T> {
+    pub fn new(learning_rate: f64) -> Sgd<T> {
+        Sgd {
+            momentum: 0.0,
+            nesterov: false,
+            weight_decay: 0.0,
+            core: OptimizerCore::new(learning_rate),
+        }
+    }
+
+    pub fn momentum(mut self, momentum: f64) -> Sgd<T> {
+        self.momentum = momentum;
+        self
+    }
+
+    pub fn nesterov(mut self, nesterov: bool) -> Sgd<T> {
+        self.nesterov = nesterov;
+        self
+    }
+
+    pub fn weight_decay(mut self, weight_decay: f64) -> Sgd<T> {
+        self.weight_decay = weight_decay;
+        self
+    }
+}
+
+impl<T: Float> Optimizer<T> for Sgd<T> {
+    fn step_parameters(&mut self, params: Vec<(String, &mut Tensor<T>)>) {
+        let lr = T::from_f64(self.core.learning_rate);
+        let momentum = T::from_f64(self.momentum);
+        let weight_decay = T::from_f64(self.weight_decay);
+        for (name, param) in params {
+            let grad = match gradient(param) {
+                Some(grad) => grad,
+                None => continue,
+            };
+            let shape = param.shape().to_vec();
+            let p = param.data_mut();
+            let mut buf = if self.momentum != 0.0 {
+                self.core.buffers.take(&name, "momentum", p.len())
+            } else {
+                Vec::new()
+            };
+            for i in 0..p.len() {
+                let mut g = grad[i] + weight_decay * p[i];
+                if self.momentum != 0.0 {
+                    buf[i] = momentum * buf[i] + g;
+                    g = if self.nesterov {
+                        g + momentum * buf[i]
+                    } else {
+                        buf[i]
+                    };
+                }
+                p[i] = p[i] - lr * g;
+            }
+            if self.momentum != 0.0 {
+                self.core.buffers.put(&name, "momentum", &shape, buf);
+            }
+        }
+    }
+
+    fn core(&self) -> &OptimizerCore<T> {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut OptimizerCore<T> {
+        &mut self.core
+    }
+}
@@ -0,0 +1,171 @@
+use crate::dtype::Float;
+use crate::tensor::Tensor;
+
+// Seedable pseudo-random number generator (xoshiro256**). The same seed always gives
+// the same sequence on every platform, so runs can be reproduced exactly.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: [u64; 4],
+    // Second value of the last Box-Muller pair, returned by the next call to `normal`
+    spare_normal: Option<f64>,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // Expand the seed with splitmix64, which never yields the all-zero state
+        let mut x = seed;
+        let mut state = [0; 4];
+        for word in &mut state {
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            *word = z ^ (z >> 31);
+        }
+        Rng {
+            state,
+            spare_normal: None,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    // Uniform in [0, 1), using the top 53 bits so every value is exactly representable
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Uniform in [low, high)
+    pub fn uniform(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+
+    // Uniform integer in [0, n) without modulo bias, `n` must be positive
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "Cannot draw from an empty range.");
+        // Reject the values of the last incomplete block of `n`
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+
+    // Standard normal sample using the Box-Muller transform
+    pub fn normal(&mut self) -> f64 {
+        if let Some(z) = self.spare_normal.take() {
+            return z;
+        }
+        // 1 - u lies in (0, 1], so the logarithm is finite
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        let radius = (-2.0 * u1.ln()).sqrt();
+        let angle = 2.0 * std::f64::consts::PI * u2;
+        self.spare_normal = Some(radius * angle.sin());
+        radius * angle.cos()
+    }
+}
+
+impl<T: Float> Tensor<T> {
+    // ========================================================================
+    // Random tensors
+    // Uniform samples in [0, 1)
+    pub fn rand(shape: Vec<usize>, rng: &mut Rng) -> Tensor<T> {
+        Tensor::uniform(shape, T::zero(), T::one(), rng)
+    }
+
+    // Uniform samples in [low, high)
+    pub fn uniform(shape: Vec<usize>, low: T, high: T, rng: &mut Rng) -> Tensor<T> {
+        let (low, high) = (low.to_f64(), high.to_f64());
+        let numel = shape.iter().product();
+        let mut sample = || loop {
+            // Rounding, in the draw or in the cast to `T`, can reach `high`. Redraw then,
+            // which ends since `low` itself is a possible draw.
+            let x = T::from_f64(rng.uniform(low, high));
+            if x.to_f64() < high || low >= high {
+                return x;
+            }
+        };
+        let data = (0..numel).map(|_| sample()).collect();
+        Tensor::from_data(shape, data)
+    }
+
+    // Standard normal samples
+    pub fn randn(shape: Vec<usize>, rng: &mut Rng) -> Tensor<T> {
+        Tensor::normal(shape, T::zero(), T::one(), rng)
+    }
+
+    // Normal samples with the given mean and standard deviation
+    pub fn normal(shape: Vec<usize>, mean: T, std: T, rng: &mut Rng) -> Tensor<T> {
+        let (mean, std) = (mean.to_f64(), std.to_f64());
+        let numel = shape.iter().product();
+        let data = (0..numel)
+            .map(|_| T::from_f64(mean + std * rng.normal()))
+            .collect();
+        Tensor::from_data(shape, data)
+    }
+}
+
+impl Tensor<bool> {
+    // Every element is true with probability `p`
+    pub fn bernoulli(shape: Vec<usize>, p: f64, rng: &mut Rng) -> Tensor<bool> {
+        let numel = shape.iter().product();
+        let data = (0..numel).map(|_| rng.next_f64() < p).collect();
+        Tensor::from_data(shape, data)
+    }
+}
+
+impl Tensor<i64> {
+    // Uniform integers in [low, high)
+    pub fn randint(shape: Vec<usize>, low: i64, high: i64, rng: &mut Rng) -> Tensor<i64> {
+        assert!(
+            low < high,
+            "Cannot draw from the empty range {}..{}.",
+            low,
+            high
+        );
+        let span = high.wrapping_sub(low) as u64;
+        let numel = shape.iter().product();
+        let data = (0..numel)
+            .map(|_| low.wrapping_add(rng.below(span) as i64))
+            .collect();
+        Tensor::from_data(shape, data)
+    }
+
+    // Random permutation of 0..n
+    pub fn randperm(n: usize, rng: &mut Rng) -> Tensor<i64> {
+        let mut data: Vec<i64> = (0..n as i64).collect();
+        // Fisher-Yates shuffle
+        for i in (1..n).rev() {
+            let j = rng.below(i as u64 + 1) as usize;
+            data.swap(i, j);
+        }
+        Tensor::from_data(vec![n], data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uniform_excludes_high_after_rounding() {
+        // Most draws between two adjacent f32 values round to one of them
+        let (low, high) = (1.0f32, 1.0f32 + f32::EPSILON);
+        let samples = Tensor::uniform(vec![1000], low, high, &mut Rng::new(0));
+        assert!(samples.iter().all(|x| x == low));
+    }
+}
@@ -1,220 +1,501 @@
-pub struct Tensor {
-    pub shape: Vec<usize>,
-    pub data: Vec<f64>,
+mod compare;
+mod create;
+mod format;
+mod index;
+mod join;
+mod matmul;
+mod ops;
+mod reduce;
+mod unary;
+mod view;
+
+pub use format::{print_options, set_print_options, PrintOptions};
+pub use index::Slice;
+
+use std::rc::Rc;
+
+use crate::autograd::{self, Node};
+use crate::dtype::{CastFrom, Element, Num};
+use crate::error::{self, Result, TensorError};
+
+// A tensor is a strided view into a shared, reference-counted buffer. Views created by
+// broadcasting, transposing, slicing or reshaping share the buffer of the tensor they
+// come from, element `[i0, i1, ...]` lives at `offset + i0 * strides[0] + i1 * strides[1] + ...`.
+// Writes through `data_mut` copy the buffer first if it is shared (copy on write).
+// The element type defaults to `f64`, see `dtype` for the supported types.
+pub struct Tensor<T = f64> {
+    storage: Rc<Vec<T>>,
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+    offset: usize,
+    pub(crate) node: Option<Rc<Node<T>>>,
+}
+
+// Row-major strides of a contiguous tensor with the given shape
+pub(crate) fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for i in (1..shape.len()).rev() {
+        strides[i - 1] = strides[i] * shape[i];
+    }
+    strides
+}
+
+// Fills in the dimension at `inferred` (if any) so that `shape` holds exactly `len`
+// elements, the placeholder at `inferred` is ignored
+pub(crate) fn infer_shape(
+    mut shape: Vec<usize>,
+    inferred: Option<usize>,
+    len: usize,
+) -> Result<Vec<usize>> {
+    if let Some(index) = inferred {
+        let known_product: usize = (0..shape.len())
+            .filter(|&i| i != index)
+            .map(|i| shape[i])
+            .product();
+        if known_product == 0 || !len.is_multiple_of(known_product) {
+            return Err(TensorError::InferDimension { shape, len });
+        }
+        shape[index] = len / known_product;
+    }
+    if shape.iter().product::<usize>() != len {
+        return Err(TensorError::DataLength { shape, len });
+    }
+    Ok(shape)
+}
+
+// Shape two tensors broadcast to, aligning dimensions from the right
+pub(crate) fn broadcast_shape(shape1: &[usize], shape2: &[usize]) -> Result<Vec<usize>> {
+    let len1 = shape1.len();
+    let len2 = shape2.len();
+    let max_len = std::cmp::max(len1, len2);
+    let mut result_shape = vec![0; max_len];
+
+    for i in 0..max_len {
+        let dim1 = if i < len1 { shape1[len1 - 1 - i] } else { 1 };
+        let dim2 = if i < len2 { shape2[len2 - 1 - i] } else { 1 };
+
+        if !(dim1 == dim2 || dim1 == 1 || dim2 == 1) {
+            return Err(TensorError::NotBroadcastable {
+                lhs: shape1.to_vec(),
+                rhs: shape2.to_vec(),
+                axis: max_len - 1 - i,
+            });
+        }
+
+        // A dimension of size one stretches to the other, also to zero
+        result_shape[max_len - 1 - i] = if dim1 == 1 { dim2 } else { dim1 };
+    }
+    Ok(result_shape)
+}
+
+// Strides that expand a view with `shape` and `strides` to `target` without copying:
+// new leading dimensions and dimensions of size one are repeated with a stride of zero
+pub(crate) fn broadcast_strides(
+    shape: &[usize],
+    strides: &[usize],
+    target: &[usize],
+) -> Result<Vec<usize>> {
+    if target.len() < shape.len() {
+        return Err(TensorError::ShapeMismatch {
+            lhs: shape.to_vec(),
+            rhs: target.to_vec(),
+        });
+    }
+    let lead = target.len() - shape.len();
+    let mut result = vec![0; target.len()];
+    for i in 0..shape.len() {
+        if shape[i] == target[lead + i] {
+            result[lead + i] = strides[i];
+        } else if shape[i] != 1 {
+            return Err(TensorError::NotBroadcastable {
+                lhs: shape.to_vec(),
+                rhs: target.to_vec(),
+                axis: lead + i,
+            });
+        }
+    }
+    Ok(result)
+}
+
+// Storage offsets of the elements of a strided view, in row-major order
+pub(crate) struct StridedIter<'a> {
+    shape: &'a [usize],
+    strides: &'a [usize],
+    index: Vec<usize>,
+    next: Option<usize>,
+}
+
+impl<'a> StridedIter<'a> {
+    pub(crate) fn new(shape: &'a [usize], strides: &'a [usize], offset: usize) -> Self {
+        let empty = shape.contains(&0);
+        StridedIter {
+            shape,
+            strides,
+            index: vec![0; shape.len()],
+            next: if empty { None } else { Some(offset) },
+        }
+    }
+}
+
+impl Iterator for StridedIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let current = self.next?;
+        // Advance the multi-index like an odometer, last axis fastest
+        let mut pos = current;
+        self.next = None;
+        for axis in (0..self.shape.len()).rev() {
+            self.index[axis] += 1;
+            pos += self.strides[axis];
+            if self.index[axis] < self.shape[axis] {
+                self.next = Some(pos);
+                break;
+            }
+            pos -= self.strides[axis] * self.shape[axis];
+            self.index[axis] = 0;
+        }
+        Some(current)
+    }
 }
 
-impl Tensor {
+impl<T: Element> Tensor<T> {
     // ========================================================================
     // Tensor creation
     // Constructor for a tensor with a given shape, initialized to zeros
-    pub fn new(shape: Vec<usize>) -> Tensor {
-        assert!(
-            !shape.contains(&0),
-            "Shape dimensions must be positive and cannot include zero."
-        );
-        let total_size: usize = shape.iter().product();
+    #[track_caller]
+    pub fn new(shape: Vec<usize>) -> Tensor<T> {
+        error::unwrap(Tensor::try_new(shape))
+    }
+
+    pub fn try_new(shape: Vec<usize>) -> Result<Tensor<T>> {
+        Tensor::try_zeros(shape)
+    }
+
+    // Constructor for a tensor with a given shape and initial data
+    // This requires that the length of data matches the product of the shape's dimensions.
+    // Dimensions of size zero give an empty tensor, use `reshape` with -1 to infer one.
+    #[track_caller]
+    pub fn from_data(shape: Vec<usize>, data: Vec<T>) -> Tensor<T> {
+        error::unwrap(Tensor::try_from_data(shape, data))
+    }
+
+    pub fn try_from_data(shape: Vec<usize>, data: Vec<T>) -> Result<Tensor<T>> {
+        let shape = infer_shape(shape, None, data.len())?;
+        Ok(Tensor {
+            strides: contiguous_strides(&shape),
+            shape,
+            storage: Rc::new(data),
+            offset: 0,
+            node: None,
+        })
+    }
+
+    // 0-D tensor holding a single value, with shape []
+    pub fn scalar(value: T) -> Tensor<T> {
+        Tensor::from_data(Vec::new(), vec![value])
+    }
+
+    // The value of a single-element tensor, e.g. a 0-D loss
+    #[track_caller]
+    pub fn item(&self) -> T {
+        error::unwrap(self.try_item())
+    }
+
+    pub fn try_item(&self) -> Result<T> {
+        if self.numel() != 1 {
+            return Err(TensorError::NotScalar {
+                shape: self.shape.clone(),
+            });
+        }
+        Ok(self.storage[self.offset])
+    }
+
+    // View of the same storage with a different layout, not connected to the graph
+    pub(crate) fn as_strided(
+        &self,
+        shape: Vec<usize>,
+        strides: Vec<usize>,
+        offset: usize,
+    ) -> Tensor<T> {
         Tensor {
+            storage: self.storage.clone(),
             shape,
-            data: vec![0.0; total_size],
+            strides,
+            offset,
+            node: None,
         }
     }
 
-    // Constructor for a tensor with a given shape and initial data
-    // This requires that the length of data matches the product of the shape's dimensions
-    pub fn from_data(mut shape: Vec<usize>, data: Vec<f64>) -> Tensor {
-        let zero_count = shape.iter().filter(|&&x| x == 0).count();
-        assert!(
-            zero_count <= 1,
-            "Only one dimension can be inferred (zero)."
-        );
-
-        if zero_count == 1 {
-            let known_product: usize = shape.iter().filter(|&&x| x != 0).product();
-            let inferred_index = shape.iter().position(|&x| x == 0).unwrap();
-            shape[inferred_index] = data.len() / known_product;
-        }
-        assert_eq!(
-            shape.iter().product::<usize>(),
-            data.len(),
-            "The product of the shape must match the length of the data."
-        );
-        Tensor { shape, data }
+    // Zero-copy view of this tensor expanded to `shape`, not connected to the graph
+    pub(crate) fn broadcast_view(&self, shape: &[usize]) -> Result<Tensor<T>> {
+        let strides = broadcast_strides(&self.shape, &self.strides, shape)?;
+        Ok(self.as_strided(shape.to_vec(), strides, self.offset))
     }
 
     // ========================================================================
-    // Tensor operations
-    fn elemwise_with_broadcast<F>(&self, other: &Tensor, op: F) -> Tensor
-    where
-        F: Fn(f64, f64) -> f64,
-    {
-        let len1 = self.shape.len();
-        let len2 = other.shape.len();
-        let max_len = std::cmp::max(len1, len2);
-        let mut result_shape = Vec::with_capacity(max_len);
-
-        let mut stride1 = 1;
-        let mut stride2 = 1;
-        let mut expanded_data1 = self.data.clone();
-        let mut expanded_data2 = other.data.clone();
-
-        for i in 0..max_len {
-            let dim1 = if i < len1 {
-                self.shape[len1 - 1 - i]
-            } else {
-                1
-            };
-            let dim2 = if i < len2 {
-                other.shape[len2 - 1 - i]
-            } else {
-                1
-            };
-
-            assert!(
-                dim1 == dim2 || dim1 == 1 || dim2 == 1,
-                "Shapes could not be broadcast together."
-            );
-
-            result_shape.insert(0, std::cmp::max(dim1, dim2));
-
-            if dim1 == 1 && dim2 != 1 {
-                expanded_data1 = expanded_data1
-                    .chunks(stride1)
-                    .flat_map(|chunk| chunk.iter().cycle().take(stride1 * dim2))
-                    .copied()
-                    .collect();
-            }
+    // Layout
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
 
-            if dim2 == 1 && dim1 != 1 {
-                expanded_data2 = expanded_data2
-                    .chunks(stride2)
-                    .flat_map(|chunk| chunk.iter().cycle().take(stride2 * dim1))
-                    .copied()
-                    .collect();
-            }
+    pub fn strides(&self) -> &[usize] {
+        &self.strides
+    }
+
+    // Position of the first element in the underlying storage
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn ndim(&self) -> usize {
+        self.shape.len()
+    }
+
+    // Number of elements
+    pub fn numel(&self) -> usize {
+        self.shape.iter().product()
+    }
 
-            stride1 *= dim1;
-            stride2 *= dim2;
+    // Whether the elements are laid out in row-major order without gaps
+    pub fn is_contiguous(&self) -> bool {
+        let expected = contiguous_strides(&self.shape);
+        self.shape
+            .iter()
+            .zip(self.strides.iter().zip(&expected))
+            .all(|(&dim, (&stride, &expected))| dim == 1 || stride == expected)
+    }
+
+    // Contiguous elements as a slice, or `None` if this is a strided view
+    pub fn as_slice(&self) -> Option<&[T]> {
+        // Empty views may start past the end of the storage
+        if self.numel() == 0 {
+            Some(&[])
+        } else if self.is_contiguous() {
+            Some(&self.storage[self.offset..self.offset + self.numel()])
+        } else {
+            None
         }
+    }
 
-        let result_data = expanded_data1
-            .into_iter()
-            .zip(expanded_data2)
-            .map(|(x, y)| op(x, y))
-            .collect();
+    pub(crate) fn offsets(&self) -> StridedIter<'_> {
+        StridedIter::new(&self.shape, &self.strides, self.offset)
+    }
 
-        Tensor {
-            shape: result_shape,
-            data: result_data,
+    // Elements in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.offsets().map(move |i| self.storage[i])
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        match self.as_slice() {
+            Some(slice) => slice.to_vec(),
+            None => self.iter().collect(),
         }
     }
 
-    // ========================================================================
-    // Matrix operations
-    pub fn matmul(&self, other: &Tensor) -> Tensor {
-        assert_eq!(self.shape.len(), 2, "First tensor is not a 2D matrix.");
-        assert_eq!(other.shape.len(), 2, "Second tensor is not a 2D matrix.");
-        assert_eq!(
-            self.shape[1], other.shape[0],
-            "Dimensions do not match for matrix multiplication."
-        );
-
-        let result_rows = self.shape[0];
-        let result_cols = other.shape[1];
-        let mut result_data = vec![0.0; result_rows * result_cols];
-
-        for i in 0..result_rows {
-            for j in 0..result_cols {
-                let mut sum = 0.0;
-                for k in 0..self.shape[1] {
-                    sum += self.data[i * self.shape[1] + k] * other.data[k * other.shape[1] + j];
-                }
-                result_data[i * result_cols + j] = sum;
-            }
+    // Tensor with the same values laid out contiguously, copying only if needed
+    pub fn contiguous(&self) -> Tensor<T> {
+        if self.is_contiguous() {
+            return self.clone();
         }
+        let mut result = Tensor::from_data(self.shape.clone(), self.to_vec());
+        result.node = self.node.clone();
+        result
+    }
 
-        Tensor {
-            shape: vec![result_rows, result_cols],
-            data: result_data,
+    // Mutable access to the elements in row-major order. Strided views are compacted
+    // and shared storage is copied first, so writes never affect other tensors.
+    // Writes are not recorded on the graph.
+    pub fn data_mut(&mut self) -> &mut [T] {
+        let numel = self.numel();
+        if !self.is_contiguous() || self.offset != 0 || self.storage.len() != numel {
+            self.storage = Rc::new(self.to_vec());
+            self.strides = contiguous_strides(&self.shape);
+            self.offset = 0;
         }
+        Rc::make_mut(&mut self.storage).as_mut_slice()
     }
 
-    // Matrix addition
-    pub fn matadd(&self, other: &Tensor) -> Tensor {
-        assert_eq!(
-            self.shape, other.shape,
-            "Shapes do not match for matrix addition."
-        );
-
-        let result_data: Vec<f64> = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(&x, &y)| x + y)
-            .collect();
+    // ========================================================================
+    // Tensor operations
+    #[track_caller]
+    pub(crate) fn elemwise_with_broadcast<U, F>(&self, other: &Tensor<T>, op: F) -> Tensor<U>
+    where
+        U: Element,
+        F: Fn(T, T) -> U,
+    {
+        error::unwrap(self.try_elemwise_with_broadcast(other, op))
+    }
 
-        Tensor {
-            shape: self.shape.clone(),
-            data: result_data,
+    pub(crate) fn try_elemwise_with_broadcast<U, F>(
+        &self,
+        other: &Tensor<T>,
+        op: F,
+    ) -> Result<Tensor<U>>
+    where
+        U: Element,
+        F: Fn(T, T) -> U,
+    {
+        if self.shape == other.shape {
+            if let (Some(a), Some(b)) = (self.as_slice(), other.as_slice()) {
+                let result_data = a.iter().zip(b).map(|(&x, &y)| op(x, y)).collect();
+                return Tensor::try_from_data(self.shape.clone(), result_data);
+            }
         }
-    }
-    // Matrix subtraction
-    pub fn matsub(&self, other: &Tensor) -> Tensor {
-        assert_eq!(
-            self.shape, other.shape,
-            "Shapes do not match for matrix addition."
-        );
-
-        let result_data: Vec<f64> = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(&x, &y)| x - y)
+
+        // Broadcast dimensions get a stride of zero, so no data is expanded
+        let result_shape = broadcast_shape(&self.shape, &other.shape)?;
+        let lhs = self.broadcast_view(&result_shape)?;
+        let rhs = other.broadcast_view(&result_shape)?;
+        let result_data = lhs
+            .offsets()
+            .zip(rhs.offsets())
+            .map(|(i, j)| op(self.storage[i], other.storage[j]))
             .collect();
 
-        Tensor {
-            shape: self.shape.clone(),
-            data: result_data,
-        }
+        Tensor::try_from_data(result_shape, result_data)
     }
-}
 
-impl std::ops::Mul for Tensor {
-    type Output = Tensor;
+    // Applies `op` to every element, without recording it on the graph
+    pub(crate) fn map<U, F>(&self, op: F) -> Tensor<U>
+    where
+        U: Element,
+        F: Fn(T) -> U,
+    {
+        Tensor::from_data(self.shape.clone(), self.iter().map(op).collect())
+    }
 
-    fn mul(self, other: Tensor) -> Tensor {
-        self.elemwise_with_broadcast(&other, |x, y| x * y)
+    // Element-wise conversion to another element type, not recorded on the graph
+    pub fn cast<U>(&self) -> Tensor<U>
+    where
+        U: Element + CastFrom<T>,
+    {
+        self.map(U::cast_from)
     }
 }
 
-impl std::ops::Div for Tensor {
-    type Output = Tensor;
+impl<T: Num> Tensor<T> {
+    // Sums a broadcast result back down to `shape`, the inverse of broadcasting to
+    // compute the gradient of an operand that was expanded along some dimensions
+    pub(crate) fn sum_to_shape(&self, shape: &[usize]) -> Tensor<T> {
+        if self.shape == shape {
+            return self.detach();
+        }
+        let mut result_data = vec![T::zero(); shape.iter().product()];
+        // Walking the result through a broadcast view visits each of its elements once
+        // for every element of `self` that was expanded from it
+        let strides = error::unwrap(broadcast_strides(
+            shape,
+            &contiguous_strides(shape),
+            &self.shape,
+        ));
+        for (i, x) in StridedIter::new(&self.shape, &strides, 0).zip(self.iter()) {
+            result_data[i] = result_data[i] + x;
+        }
 
-    fn div(self, other: Tensor) -> Tensor {
-        self.elemwise_with_broadcast(&other, |x, y| x / y)
+        Tensor::from_data(shape.to_vec(), result_data)
+    }
+
+    // Records a broadcasting binary op whose local derivatives with respect to each
+    // operand are given by `da(x, y)` and `db(x, y)`
+    fn binary_op<F, DA, DB>(&self, other: &Tensor<T>, op: F, da: DA, db: DB) -> Result<Tensor<T>>
+    where
+        F: Fn(T, T) -> T,
+        DA: Fn(T, T) -> T + 'static,
+        DB: Fn(T, T) -> T + 'static,
+    {
+        let result = self.try_elemwise_with_broadcast(other, op)?;
+        let (a, b) = (self.detach(), other.detach());
+        Ok(autograd::record(result, &[self, other], move |grad| {
+            let ga = a.elemwise_with_broadcast(&b, &da);
+            let gb = a.elemwise_with_broadcast(&b, &db);
+            vec![
+                grad.elemwise_with_broadcast(&ga, |g, d| g * d)
+                    .sum_to_shape(&a.shape),
+                grad.elemwise_with_broadcast(&gb, |g, d| g * d)
+                    .sum_to_shape(&b.shape),
+            ]
+        }))
     }
-}
 
-impl std::ops::Add for Tensor {
-    type Output = Tensor;
+    // Broadcasting element-wise arithmetic, also available through the operator traits
+    pub fn try_add(&self, other: &Tensor<T>) -> Result<Tensor<T>> {
+        self.binary_op(other, |x, y| x + y, |_, _| T::one(), |_, _| T::one())
+    }
 
-    fn add(self, other: Tensor) -> Tensor {
-        self.elemwise_with_broadcast(&other, |x, y| x + y)
+    pub fn try_sub(&self, other: &Tensor<T>) -> Result<Tensor<T>> {
+        let minus_one = T::zero() - T::one();
+        self.binary_op(other, |x, y| x - y, |_, _| T::one(), move |_, _| minus_one)
     }
-}
 
-impl std::ops::Sub for Tensor {
-    type Output = Tensor;
+    pub fn try_mul(&self, other: &Tensor<T>) -> Result<Tensor<T>> {
+        self.binary_op(other, |x, y| x * y, |_, y| y, |x, _| x)
+    }
+
+    pub fn try_div(&self, other: &Tensor<T>) -> Result<Tensor<T>> {
+        self.binary_op(
+            other,
+            |x, y| x / y,
+            |_, y| T::one() / y,
+            |x, y| T::zero() - x / (y * y),
+        )
+    }
+
+    // ========================================================================
+    // Matrix operations
+    // Matrix addition
+    #[track_caller]
+    pub fn matadd(&self, other: &Tensor<T>) -> Tensor<T> {
+        error::unwrap(self.try_matadd(other))
+    }
+
+    pub fn try_matadd(&self, other: &Tensor<T>) -> Result<Tensor<T>> {
+        if self.shape != other.shape {
+            return Err(TensorError::ShapeMismatch {
+                lhs: self.shape.clone(),
+                rhs: other.shape.clone(),
+            });
+        }
+
+        let result_data: Vec<T> = self.iter().zip(other.iter()).map(|(x, y)| x + y).collect();
+
+        let result = Tensor::from_data(self.shape.clone(), result_data);
+        Ok(autograd::record(result, &[self, other], |grad| {
+            vec![grad.detach(), grad.detach()]
+        }))
+    }
+    // Matrix subtraction
+    #[track_caller]
+    pub fn matsub(&self, other: &Tensor<T>) -> Tensor<T> {
+        error::unwrap(self.try_matsub(other))
+    }
+
+    pub fn try_matsub(&self, other: &Tensor<T>) -> Result<Tensor<T>> {
+        if self.shape != other.shape {
+            return Err(TensorError::ShapeMismatch {
+                lhs: self.shape.clone(),
+                rhs: other.shape.clone(),
+            });
+        }
+
+        let result_data: Vec<T> = self.iter().zip(other.iter()).map(|(x, y)| x - y).collect();
 
-    fn sub(self, other: Tensor) -> Tensor {
-        self.elemwise_with_broadcast(&other, |x, y| x - y)
+        let result = Tensor::from_data(self.shape.clone(), result_data);
+        Ok(autograd::record(result, &[self, other], |grad| {
+            vec![grad.detach(), grad.map(|g| T::zero() - g)]
+        }))
     }
 }
 
-impl Clone for Tensor {
+impl<T> Clone for Tensor<T> {
     fn clone(&self) -> Self {
         Tensor {
+            storage: self.storage.clone(),
             shape: self.shape.clone(),
-            data: self.data.clone(),
+            strides: self.strides.clone(),
+            offset: self.offset,
+            node: self.node.clone(),
         }
     }
 }
@@ -0,0 +1,141 @@
+use super::reduce::is_nan;
+use super::{broadcast_shape, Tensor};
+use crate::autograd;
+use crate::dtype::{Element, Float};
+use crate::error::{self, Result};
+
+// Broadcasting element-wise comparisons producing boolean masks, e.g. `a.lt(&b)` is
+// true where `a < b`
+macro_rules! impl_compare {
+    ($($method:ident, $try_method:ident, $op:tt);* $(;)?) => {
+        impl<T: Element> Tensor<T> {
+            $(
+                #[track_caller]
+                pub fn $method(&self, other: &Tensor<T>) -> Tensor<bool> {
+                    error::unwrap(self.$try_method(other))
+                }
+
+                pub fn $try_method(&self, other: &Tensor<T>) -> Result<Tensor<bool>> {
+                    self.try_elemwise_with_broadcast(other, |x, y| x $op y)
+                }
+            )*
+        }
+    };
+}
+
+impl_compare! {
+    eq, try_eq, ==;
+    ne, try_ne, !=;
+    lt, try_lt, <;
+    le, try_le, <=;
+    gt, try_gt, >;
+    ge, try_ge, >=;
+}
+
+impl<T: Element> Tensor<T> {
+    // ========================================================================
+    // Selection
+    // Picks `a` where `cond` is true and `b` elsewhere, all three broadcast together.
+    // Gradients flow to the operand that was picked.
+    #[track_caller]
+    pub fn where_(cond: &Tensor<bool>, a: &Tensor<T>, b: &Tensor<T>) -> Tensor<T> {
+        error::unwrap(Tensor::try_where(cond, a, b))
+    }
+
+    pub fn try_where(cond: &Tensor<bool>, a: &Tensor<T>, b: &Tensor<T>) -> Result<Tensor<T>> {
+        let shape = broadcast_shape(&broadcast_shape(&cond.shape, &a.shape)?, &b.shape)?;
+        let mask = cond.broadcast_view(&shape)?;
+        let (lhs, rhs) = (a.broadcast_view(&shape)?, b.broadcast_view(&shape)?);
+        let result_data = mask
+            .iter()
+            .zip(lhs.iter().zip(rhs.iter()))
+            .map(|(c, (x, y))| if c { x } else { y })
+            .collect();
+
+        let result = Tensor::from_data(shape, result_data);
+        Ok(autograd::record(result, &[a, b], move |grad| {
+            let pick = |take: bool| {
+                let data = grad
+                    .iter()
+                    .zip(mask.iter())
+                    .map(|(g, c)| if c == take { g } else { T::zero() })
+                    .collect();
+                Tensor::from_data(grad.shape.clone(), data)
+            };
+            vec![pick(true), pick(false)]
+        }))
+    }
+
+    // Replaces the elements where `mask` is true with `value`
+    #[track_caller]
+    pub fn masked_fill(&self, mask: &Tensor<bool>, value: T) -> Tensor<T> {
+        error::unwrap(self.try_masked_fill(mask, value))
+    }
+
+    pub fn try_masked_fill(&self, mask: &Tensor<bool>, value: T) -> Result<Tensor<T>> {
+        Tensor::try_where(mask, &Tensor::scalar(value), self)
+    }
+}
+
+impl Tensor<bool> {
+    // ========================================================================
+    // Logical operations
+    #[track_caller]
+    pub fn logical_and(&self, other: &Tensor<bool>) -> Tensor<bool> {
+        error::unwrap(self.try_logical_and(other))
+    }
+
+    pub fn try_logical_and(&self, other: &Tensor<bool>) -> Result<Tensor<bool>> {
+        self.try_elemwise_with_broadcast(other, |x, y| x && y)
+    }
+
+    #[track_caller]
+    pub fn logical_or(&self, other: &Tensor<bool>) -> Tensor<bool> {
+        error::unwrap(self.try_logical_or(other))
+    }
+
+    pub fn try_logical_or(&self, other: &Tensor<bool>) -> Result<Tensor<bool>> {
+        self.try_elemwise_with_broadcast(other, |x, y| x || y)
+    }
+
+    pub fn logical_not(&self) -> Tensor<bool> {
+        self.map(|x| !x)
+    }
+
+    // Whether every element is true, also for an empty tensor
+    pub fn all(&self) -> bool {
+        self.iter().all(|x| x)
+    }
+
+    // Whether any element is true
+    pub fn any(&self) -> bool {
+        self.iter().any(|x| x)
+    }
+}
+
+impl<T: Float> Tensor<T> {
+    // Whether `|self - other| <= atol + rtol * |other|` holds for every element after
+    // broadcasting, NaN is never close to anything
+    #[track_caller]
+    pub fn allclose(&self, other: &Tensor<T>, rtol: T, atol: T) -> bool {
+        error::unwrap(self.try_allclose(other, rtol, atol))
+    }
+
+    pub fn try_allclose(&self, other: &Tensor<T>, rtol: T, atol: T) -> Result<bool> {
+        let close = self.try_elemwise_with_broadcast(other, |x, y| {
+            // Also covers infinities of the same sign, whose difference is NaN
+            x == y || (x - y).abs() <= atol + rtol * y.abs()
+        })?;
+        Ok(close.all())
+    }
+
+    pub fn isnan(&self) -> Tensor<bool> {
+        self.map(is_nan)
+    }
+
+    // True for positive and negative infinity
+    pub fn isinf(&self) -> Tensor<bool> {
+        let inf = T::from_f64(f64::INFINITY);
+        self.map(move |x| x.abs() == inf)
+    }
+}
@@ -0,0 +1,245 @@
+use super::Tensor;
+use crate::autograd;
+use crate::dtype::{CastFrom, Element, Float, Num};
+use crate::error::{self, Result, TensorError};
+
+// Builds a tensor from nested array literals, inferring the shape from the nesting:
+// `tensor![[1.0, 2.0], [3.0, 4.0]]` has shape [2, 2]. Rows of different lengths panic.
+#[macro_export]
+macro_rules! tensor {
+    ($([$($row:tt)*]),+ $(,)?) => {
+        $crate::tensor::Tensor::stack(&[$(&$crate::tensor![$($row)*]),+], 0)
+    };
+    ($($x:expr),* $(,)?) => {{
+        let data = vec![$($x),*];
+        $crate::tensor::Tensor::from_data(vec![data.len()], data)
+    }};
+}
+
+impl<T: Element> Tensor<T> {
+    // ========================================================================
+    // Filled tensors
+    // Every element set to `value`
+    #[track_caller]
+    pub fn full(shape: Vec<usize>, value: T) -> Tensor<T> {
+        error::unwrap(Tensor::try_full(shape, value))
+    }
+
+    pub fn try_full(shape: Vec<usize>, value: T) -> Result<Tensor<T>> {
+        let total_size: usize = shape.iter().product();
+        Tensor::try_from_data(shape, vec![value; total_size])
+    }
+
+    #[track_caller]
+    pub fn zeros(shape: Vec<usize>) -> Tensor<T> {
+        error::unwrap(Tensor::try_zeros(shape))
+    }
+
+    pub fn try_zeros(shape: Vec<usize>) -> Result<Tensor<T>> {
+        Tensor::try_full(shape, T::zero())
+    }
+
+    #[track_caller]
+    pub fn ones(shape: Vec<usize>) -> Tensor<T> {
+        error::unwrap(Tensor::try_ones(shape))
+    }
+
+    pub fn try_ones(shape: Vec<usize>) -> Result<Tensor<T>> {
+        Tensor::try_full(shape, T::one())
+    }
+
+    // Tensors with the shape of `self`, not connected to the graph
+    pub fn full_like(&self, value: T) -> Tensor<T> {
+        Tensor::from_data(self.shape.clone(), vec![value; self.numel()])
+    }
+
+    pub fn zeros_like(&self) -> Tensor<T> {
+        self.full_like(T::zero())
+    }
+
+    pub fn ones_like(&self) -> Tensor<T> {
+        self.full_like(T::one())
+    }
+
+    // Identity matrix of shape [n, n]
+    #[track_caller]
+    pub fn eye(n: usize) -> Tensor<T> {
+        error::unwrap(Tensor::try_eye(n))
+    }
+
+    pub fn try_eye(n: usize) -> Result<Tensor<T>> {
+        let mut result = Tensor::try_zeros(vec![n, n])?;
+        let data = result.data_mut();
+        for i in 0..n {
+            data[i * n + i] = T::one();
+        }
+        Ok(result)
+    }
+
+    // Like `numpy.diag`: the diagonal of a matrix as a view, or a square matrix with
+    // a vector on its diagonal
+    #[track_caller]
+    pub fn diag(&self) -> Tensor<T> {
+        error::unwrap(self.try_diag())
+    }
+
+    pub fn try_diag(&self) -> Result<Tensor<T>> {
+        match self.ndim() {
+            1 => {
+                let n = self.shape[0];
+                let mut result_data = vec![T::zero(); n * n];
+                for (i, x) in self.iter().enumerate() {
+                    result_data[i * n + i] = x;
+                }
+                let result = Tensor::from_data(vec![n, n], result_data);
+                Ok(autograd::record(result, &[self], |grad| vec![grad.diag()]))
+            }
+            2 => {
+                let n = self.shape[0].min(self.shape[1]);
+                let stride = self.strides[0] + self.strides[1];
+                let result = self.as_strided(vec![n], vec![stride], self.offset);
+                let shape = self.shape.clone();
+                Ok(autograd::record(result, &[self], move |grad| {
+                    // Scatter the gradient back onto the diagonal of a zero matrix
+                    let mut result_data = vec![T::zero(); shape[0] * shape[1]];
+                    for (i, g) in grad.iter().enumerate() {
+                        result_data[i * (shape[1] + 1)] = g;
+                    }
+                    vec![Tensor::from_data(shape.clone(), result_data)]
+                }))
+            }
+            _ => Err(TensorError::RankMismatch {
+                expected: 2,
+                shape: self.shape.clone(),
+            }),
+        }
+    }
+}
+
+impl<T: Num> Tensor<T> {
+    // ========================================================================
+    // Ranges
+    // Values `start, start + step, ...` up to but excluding `end`, `step` may be negative
+    #[track_caller]
+    pub fn arange(start: T, end: T, step: T) -> Tensor<T>
+    where
+        T: CastFrom<f64>,
+        f64: CastFrom<T>,
+    {
+        error::unwrap(Tensor::try_arange(start, end, step))
+    }
+
+    pub fn try_arange(start: T, end: T, step: T) -> Result<Tensor<T>>
+    where
+        T: CastFrom<f64>,
+        f64: CastFrom<T>,
+    {
+        if step == T::zero() {
+            return Err(TensorError::ZeroStep);
+        }
+        let (low, high, by) = (
+            f64::cast_from(start),
+            f64::cast_from(end),
+            f64::cast_from(step),
+        );
+        let count = ((high - low) / by).ceil();
+        if count.is_infinite() {
+            return Err(TensorError::UnboundedRange);
+        }
+        // The largest allocation a `Vec<T>` allows
+        if count > (isize::MAX as usize / std::mem::size_of::<T>()) as f64 {
+            return Err(TensorError::RangeTooLong);
+        }
+        // Negative and NaN counts saturate to an empty range
+        let len = count as usize;
+        let ascending = step > T::zero();
+        // Multiples of `step` rather than a running sum, so floats do not drift
+        let mut result_data: Vec<T> = (0..len)
+            .map(|i| start + T::cast_from(i as f64) * step)
+            .collect();
+        // Rounding in the count can let one value reach past `end`
+        while let Some(&x) = result_data.last() {
+            if (ascending && x < end) || (!ascending && x > end) {
+                break;
+            }
+            result_data.pop();
+        }
+        let len = result_data.len();
+        Tensor::try_from_data(vec![len], result_data)
+    }
+}
+
+impl<T: Float> Tensor<T> {
+    // `steps` evenly spaced values from `start` to `end`, both included
+    pub fn linspace(start: T, end: T, steps: usize) -> Tensor<T> {
+        let step = if steps > 1 {
+            (end - start) / T::from_f64((steps - 1) as f64)
+        } else {
+            T::zero()
+        };
+        let mut result_data: Vec<T> = (0..steps)
+            .map(|i| start + T::from_f64(i as f64) * step)
+            .collect();
+        // Hit `end` exactly despite rounding
+        if steps > 1 {
+            result_data[steps - 1] = end;
+        }
+        Tensor::from_data(vec![steps], result_data)
+    }
+
+    // `steps` values from `base^start` to `base^end`, evenly spaced on a log scale
+    pub fn logspace(start: T, end: T, steps: usize, base: T) -> Tensor<T> {
+        Tensor::linspace(start, end, steps).map(|x| base.powf(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arange_rejects_zero_step() {
+        assert_eq!(
+            Tensor::try_arange(0.0, 1.0, 0.0).err(),
+            Some(TensorError::ZeroStep)
+        );
+        assert_eq!(
+            Tensor::try_arange(0, 1, 0).err(),
+            Some(TensorError::ZeroStep)
+        );
+    }
+
+    #[test]
+    fn arange_rejects_infinite_bounds() {
+        assert_eq!(
+            Tensor::try_arange(0.0, f64::INFINITY, 1.0).err(),
+            Some(TensorError::UnboundedRange)
+        );
+        assert_eq!(
+            Tensor::try_arange(f64::NEG_INFINITY, 0.0, 1.0).err(),
+            Some(TensorError::UnboundedRange)
+        );
+    }
+
+    #[test]
+    fn arange_rejects_ranges_too_long_to_allocate() {
+        assert_eq!(
+            Tensor::try_arange(0.0, 1e300, 1.0).err(),
+            Some(TensorError::RangeTooLong)
+        );
+        assert_eq!(
+            Tensor::try_arange(i64::MIN, i64::MAX, 1).err(),
+            Some(TensorError::RangeTooLong)
+        );
+    }
+
+    #[test]
+    fn arange_excludes_end() {
+        assert_eq!(Tensor::arange(0, 5, 2).to_vec(), vec![0, 2, 4]);
+        assert_eq!(
+            Tensor::arange(1.0, 0.0, -0.25).to_vec(),
+            vec![1.0, 0.75, 0.5, 0.25]
+        );
+        assert_eq!(Tensor::arange(0.0, -1.0, 1.0).numel(), 0);
+    }
+}
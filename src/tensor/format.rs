@@ -0,0 +1,143 @@
+use std::cell::Cell;
+use std::fmt;
+
+use super::Tensor;
+use crate::dtype::Element;
+
+// Controls how tensors are printed, like NumPy's print options. A precision given in
+// the format string, as in `{:.2}`, takes priority over `precision`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrintOptions {
+    // Digits after the decimal point of floating point elements
+    pub precision: usize,
+    // Tensors with more elements than this are summarized with `...`
+    pub threshold: usize,
+    // Number of leading and trailing entries kept of every summarized dimension
+    pub edge_items: usize,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            precision: 4,
+            threshold: 1000,
+            edge_items: 3,
+        }
+    }
+}
+
+thread_local! {
+    static PRINT_OPTIONS: Cell<PrintOptions> = Cell::new(PrintOptions::default());
+}
+
+pub fn set_print_options(options: PrintOptions) {
+    PRINT_OPTIONS.with(|current| current.set(options));
+}
+
+pub fn print_options() -> PrintOptions {
+    PRINT_OPTIONS.with(|current| current.get())
+}
+
+impl<T: Element> Tensor<T> {
+    // Positions shown along every axis, `None` marks the `...` of a summarized axis
+    fn shown_indices(&self, options: &PrintOptions) -> Vec<Vec<Option<usize>>> {
+        let summarize = self.numel() > options.threshold;
+        let edge = options.edge_items;
+        self.shape
+            .iter()
+            .map(|&size| {
+                if summarize && size > 2 * edge {
+                    let head = (0..edge).map(Some);
+                    let tail = (size - edge..size).map(Some);
+                    head.chain([None]).chain(tail).collect()
+                } else {
+                    (0..size).map(Some).collect()
+                }
+            })
+            .collect()
+    }
+
+    // Storage offsets of the shown elements in row-major order
+    fn shown_offsets(
+        &self,
+        shown: &[Vec<Option<usize>>],
+        axis: usize,
+        offset: usize,
+    ) -> Vec<usize> {
+        if axis == self.ndim() {
+            return vec![offset];
+        }
+        shown[axis]
+            .iter()
+            .flatten()
+            .flat_map(|&i| self.shown_offsets(shown, axis + 1, offset + i * self.strides[axis]))
+            .collect()
+    }
+
+    // Writes the nested brackets of `axis` and below, taking the already padded
+    // element texts from `cells`
+    fn write_nested<'a>(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        shown: &[Vec<Option<usize>>],
+        axis: usize,
+        cells: &mut impl Iterator<Item = &'a String>,
+    ) -> fmt::Result {
+        if axis == self.ndim() {
+            return write!(f, "{}", cells.next().map_or("", |cell| cell.as_str()));
+        }
+        write!(f, "[")?;
+        for (n, index) in shown[axis].iter().enumerate() {
+            if n > 0 {
+                if axis + 1 == self.ndim() {
+                    write!(f, ", ")?;
+                } else {
+                    // Blocks of higher dimensions are separated by more blank lines,
+                    // continuation lines are indented past the open brackets
+                    let newlines = "\n".repeat(self.ndim() - axis - 1);
+                    write!(f, ",{}{}", newlines, " ".repeat(axis + 1))?;
+                }
+            }
+            match index {
+                Some(_) => self.write_nested(f, shown, axis + 1, cells)?,
+                None => write!(f, "...")?,
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+// Nested brackets with the elements right-aligned to a common width:
+// [[ 1.0000, -2.0000],
+//  [ 3.0000,  4.0000]]
+impl<T: Element> fmt::Display for Tensor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = print_options();
+        let precision = f.precision().unwrap_or(options.precision);
+        let shown = self.shown_indices(&options);
+        let texts: Vec<String> = self
+            .shown_offsets(&shown, 0, self.offset)
+            .into_iter()
+            .map(|i| self.storage[i].format_with(precision))
+            .collect();
+        let width = texts.iter().map(|text| text.len()).max().unwrap_or(0);
+        let cells: Vec<String> = texts
+            .iter()
+            .map(|text| format!("{:>width$}", text, width = width))
+            .collect();
+        self.write_nested(f, &shown, 0, &mut cells.iter())
+    }
+}
+
+impl<T: Element> fmt::Debug for Tensor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tensor")
+            .field("shape", &self.shape)
+            .field("strides", &self.strides)
+            .field("offset", &self.offset)
+            .field("dtype", &format_args!("{}", T::DTYPE))
+            .field("requires_grad", &self.requires_grad())
+            .field("data", &format_args!("{}", self))
+            .finish()
+    }
+}
@@ -0,0 +1,333 @@
+use std::ops::{
+    Index, IndexMut, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
+use std::rc::Rc;
+
+use super::{contiguous_strides, StridedIter, Tensor};
+use crate::autograd;
+use crate::dtype::Element;
+use crate::error::{self, Result, TensorError};
+
+// One entry of a selector passed to `Tensor::slice`, usually built with the `s!` macro.
+// Negative indices and bounds count from the end of the axis like in Python.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Slice {
+    // Selects a single position and removes the axis
+    Index(isize),
+    // Positions `start, start + step, ...` before `end`, missing bounds default to the
+    // whole axis and out of range bounds are clamped
+    Range {
+        start: Option<isize>,
+        end: Option<isize>,
+        step: isize,
+    },
+    // Inserts an axis of size one
+    NewAxis,
+    // Stands for as many whole axes as needed to select every dimension
+    Ellipsis,
+}
+
+impl Slice {
+    // Same range with another step, other selectors are returned unchanged
+    pub fn step(self, step: isize) -> Slice {
+        match self {
+            Slice::Range { start, end, .. } => Slice::Range { start, end, step },
+            other => other,
+        }
+    }
+}
+
+impl From<isize> for Slice {
+    fn from(index: isize) -> Slice {
+        Slice::Index(index)
+    }
+}
+
+impl From<RangeFull> for Slice {
+    fn from(_: RangeFull) -> Slice {
+        Slice::Range {
+            start: None,
+            end: None,
+            step: 1,
+        }
+    }
+}
+
+impl From<Range<isize>> for Slice {
+    fn from(range: Range<isize>) -> Slice {
+        Slice::Range {
+            start: Some(range.start),
+            end: Some(range.end),
+            step: 1,
+        }
+    }
+}
+
+impl From<RangeFrom<isize>> for Slice {
+    fn from(range: RangeFrom<isize>) -> Slice {
+        Slice::Range {
+            start: Some(range.start),
+            end: None,
+            step: 1,
+        }
+    }
+}
+
+impl From<RangeTo<isize>> for Slice {
+    fn from(range: RangeTo<isize>) -> Slice {
+        Slice::Range {
+            start: None,
+            end: Some(range.end),
+            step: 1,
+        }
+    }
+}
+
+impl From<RangeInclusive<isize>> for Slice {
+    fn from(range: RangeInclusive<isize>) -> Slice {
+        let end = *range.end();
+        Slice::Range {
+            start: Some(*range.start()),
+            // `..=-1` reaches the end of the axis
+            end: if end == -1 { None } else { Some(end + 1) },
+            step: 1,
+        }
+    }
+}
+
+impl From<RangeToInclusive<isize>> for Slice {
+    fn from(range: RangeToInclusive<isize>) -> Slice {
+        Slice::from(0..=range.end)
+    }
+}
+
+// Builds a selector for `Tensor::slice` and `Tensor::slice_assign` with NumPy-like syntax,
+// `s![1.., ..;2, -1, Slice::NewAxis]` is `[1:, ::2, -1, np.newaxis]`
+#[macro_export]
+macro_rules! s {
+    ($($slice:expr $(; $step:expr)?),* $(,)?) => {
+        &[$($crate::tensor::Slice::from($slice)$(.step($step))?),*]
+    };
+}
+
+// Resolves a possibly negative `index` into an axis of the given size
+fn resolve_index(index: isize, axis: usize, size: usize) -> Result<usize> {
+    let resolved = if index < 0 {
+        index + size as isize
+    } else {
+        index
+    };
+    if resolved < 0 || resolved >= size as isize {
+        return Err(TensorError::OutOfBounds { axis, index, size });
+    }
+    Ok(resolved as usize)
+}
+
+// Layout (shape, strides, offset) of the view `slices` selects from a tensor with the
+// given layout. Axes not covered by `slices` are kept whole.
+fn slice_layout(
+    shape: &[usize],
+    strides: &[usize],
+    offset: usize,
+    slices: &[Slice],
+) -> Result<(Vec<usize>, Vec<usize>, usize)> {
+    if slices.iter().filter(|s| **s == Slice::Ellipsis).count() > 1 {
+        return Err(TensorError::MultipleEllipsis);
+    }
+    let indices = slices
+        .iter()
+        .filter(|s| matches!(s, Slice::Index(_) | Slice::Range { .. }))
+        .count();
+    if indices > shape.len() {
+        return Err(TensorError::TooManyIndices {
+            indices,
+            ndim: shape.len(),
+        });
+    }
+
+    let mut result_shape = Vec::new();
+    let mut result_strides = Vec::new();
+    let mut offset = offset;
+    let mut axis = 0;
+    for &slice in slices {
+        match slice {
+            Slice::Index(index) => {
+                offset += resolve_index(index, axis, shape[axis])? * strides[axis];
+                axis += 1;
+            }
+            Slice::Range { start, end, step } => {
+                if step <= 0 {
+                    return Err(TensorError::InvalidStep { step });
+                }
+                let size = shape[axis] as isize;
+                let clamp = |i: isize| {
+                    if i < 0 {
+                        (i + size).max(0)
+                    } else {
+                        i.min(size)
+                    }
+                };
+                let start = start.map_or(0, clamp);
+                let end = end.map_or(size, clamp);
+                let len = if end > start {
+                    (end - start + step - 1) / step
+                } else {
+                    0
+                };
+                offset += start as usize * strides[axis];
+                result_shape.push(len as usize);
+                result_strides.push(strides[axis] * step as usize);
+                axis += 1;
+            }
+            Slice::NewAxis => {
+                result_shape.push(1);
+                result_strides.push(0);
+            }
+            Slice::Ellipsis => {
+                let whole = shape.len() - indices;
+                result_shape.extend_from_slice(&shape[axis..axis + whole]);
+                result_strides.extend_from_slice(&strides[axis..axis + whole]);
+                axis += whole;
+            }
+        }
+    }
+    result_shape.extend_from_slice(&shape[axis..]);
+    result_strides.extend_from_slice(&strides[axis..]);
+    Ok((result_shape, result_strides, offset))
+}
+
+impl<T: Element> Tensor<T> {
+    // ========================================================================
+    // Indexing
+    // Storage offset of the element at a full multi-index
+    fn storage_index(&self, index: &[usize]) -> Result<usize> {
+        if index.len() != self.ndim() {
+            return Err(TensorError::RankMismatch {
+                expected: index.len(),
+                shape: self.shape.clone(),
+            });
+        }
+        let mut offset = self.offset;
+        for (axis, (&i, &size)) in index.iter().zip(&self.shape).enumerate() {
+            if i >= size {
+                return Err(TensorError::OutOfBounds {
+                    axis,
+                    index: i as isize,
+                    size,
+                });
+            }
+            offset += i * self.strides[axis];
+        }
+        Ok(offset)
+    }
+
+    // Element at a full multi-index, the fallible version of `tensor[[i, j]]`
+    pub fn try_get(&self, index: &[usize]) -> Result<T> {
+        Ok(self.storage[self.storage_index(index)?])
+    }
+
+    // View of the region selected by `slices`, see `Slice` and the `s!` macro
+    #[track_caller]
+    pub fn slice(&self, slices: &[Slice]) -> Tensor<T> {
+        error::unwrap(self.try_slice(slices))
+    }
+
+    pub fn try_slice(&self, slices: &[Slice]) -> Result<Tensor<T>> {
+        let (shape, strides, offset) =
+            slice_layout(&self.shape, &self.strides, self.offset, slices)?;
+        let result = self.as_strided(shape, strides, offset);
+
+        // The same selection on a contiguous tensor of the original shape
+        let original = self.shape.clone();
+        let region = slice_layout(&original, &contiguous_strides(&original), 0, slices)?;
+        Ok(autograd::record(result, &[self], move |grad| {
+            // Scatter the gradient back into a zero tensor of the original shape
+            let (shape, strides, offset) = &region;
+            let mut result_data = vec![T::zero(); original.iter().product()];
+            for (i, g) in StridedIter::new(shape, strides, *offset).zip(grad.iter()) {
+                result_data[i] = g;
+            }
+            vec![Tensor::from_data(original.clone(), result_data)]
+        }))
+    }
+
+    // Writes `value`, broadcast to the shape of the selected region, into the region
+    // selected by `slices`. Other tensors sharing the storage are not affected.
+    #[track_caller]
+    pub fn slice_assign(&mut self, slices: &[Slice], value: &Tensor<T>) {
+        error::unwrap(self.try_slice_assign(slices, value))
+    }
+
+    pub fn try_slice_assign(&mut self, slices: &[Slice], value: &Tensor<T>) -> Result<()> {
+        let original = self.shape.clone();
+        let (shape, strides, offset) =
+            slice_layout(&original, &contiguous_strides(&original), 0, slices)?;
+        let expanded = value
+            .broadcast_view(&shape)
+            .map_err(|_| TensorError::ShapeMismatch {
+                lhs: shape.clone(),
+                rhs: value.shape.clone(),
+            })?;
+
+        let before = self.clone();
+        // `data_mut` leaves the tensor contiguous, matching the layout computed above
+        let data = self.data_mut();
+        for (i, x) in StridedIter::new(&shape, &strides, offset).zip(expanded.iter()) {
+            data[i] = x;
+        }
+
+        if autograd::is_grad_enabled() && (before.node.is_some() || value.node.is_some()) {
+            let result = self.detach();
+            *self = autograd::record(result, &[&before, value], move |grad| {
+                // The overwritten region gets no gradient, the value gets exactly that region
+                let mut rest = grad.to_vec();
+                let region = StridedIter::new(&shape, &strides, offset);
+                let region_data = region.map(|i| std::mem::replace(&mut rest[i], T::zero()));
+                let region_grad = Tensor::from_data(shape.clone(), region_data.collect());
+                vec![Tensor::from_data(original.clone(), rest), region_grad]
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<T: Element, const N: usize> Index<[usize; N]> for Tensor<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: [usize; N]) -> &T {
+        &self[&index[..]]
+    }
+}
+
+impl<T: Element> Index<&[usize]> for Tensor<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: &[usize]) -> &T {
+        &self.storage[error::unwrap(self.storage_index(index))]
+    }
+}
+
+// Writes are not recorded on the graph and copy the storage first if it is shared
+impl<T: Element, const N: usize> IndexMut<[usize; N]> for Tensor<T> {
+    #[track_caller]
+    fn index_mut(&mut self, index: [usize; N]) -> &mut T {
+        &mut self[&index[..]]
+    }
+}
+
+impl<T: Element> IndexMut<&[usize]> for Tensor<T> {
+    #[track_caller]
+    fn index_mut(&mut self, index: &[usize]) -> &mut T {
+        let mut i = error::unwrap(self.storage_index(index));
+        // In a broadcast view one element stands for a whole axis, so give every
+        // position its own element before writing
+        let mut layout = self.shape.iter().zip(&self.strides);
+        if layout.any(|(&size, &stride)| stride == 0 && size > 1) {
+            self.data_mut();
+            i = error::unwrap(self.storage_index(index));
+        }
+        &mut Rc::make_mut(&mut self.storage)[i]
+    }
+}
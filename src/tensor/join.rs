@@ -0,0 +1,167 @@
+use super::{contiguous_strides, StridedIter, Tensor};
+use crate::autograd;
+use crate::dtype::Element;
+use crate::error::{self, Result, TensorError};
+
+impl<T: Element> Tensor<T> {
+    // ========================================================================
+    // Joining and splitting
+    // Concatenates tensors along an existing `axis`, every other dimension must match
+    #[track_caller]
+    pub fn cat(tensors: &[&Tensor<T>], axis: usize) -> Tensor<T> {
+        error::unwrap(Tensor::try_cat(tensors, axis))
+    }
+
+    pub fn try_cat(tensors: &[&Tensor<T>], axis: usize) -> Result<Tensor<T>> {
+        let first = tensors.first().ok_or(TensorError::NoTensors)?;
+        first.check_axis(axis)?;
+        for t in &tensors[1..] {
+            let same_rank = t.ndim() == first.ndim();
+            if !same_rank || (0..t.ndim()).any(|i| i != axis && t.shape[i] != first.shape[i]) {
+                return Err(TensorError::ShapeMismatch {
+                    lhs: first.shape.clone(),
+                    rhs: t.shape.clone(),
+                });
+            }
+        }
+
+        let mut shape = first.shape.clone();
+        shape[axis] = tensors.iter().map(|t| t.shape[axis]).sum();
+        let strides = contiguous_strides(&shape);
+        let mut result_data = vec![T::zero(); shape.iter().product()];
+        let mut start = 0;
+        // Each input fills the block of the result starting at `start` along `axis`
+        for t in tensors {
+            let region = StridedIter::new(&t.shape, &strides, start * strides[axis]);
+            for (i, x) in region.zip(t.iter()) {
+                result_data[i] = x;
+            }
+            start += t.shape[axis];
+        }
+
+        let sizes: Vec<usize> = tensors.iter().map(|t| t.shape[axis]).collect();
+        let result = Tensor::from_data(shape, result_data);
+        Ok(autograd::record(result, tensors, move |grad| {
+            let mut start = 0;
+            sizes
+                .iter()
+                .map(|&size| {
+                    start += size;
+                    grad.narrow(axis, start - size, size)
+                })
+                .collect()
+        }))
+    }
+
+    // Joins tensors of the same shape along a new `axis`
+    #[track_caller]
+    pub fn stack(tensors: &[&Tensor<T>], axis: usize) -> Tensor<T> {
+        error::unwrap(Tensor::try_stack(tensors, axis))
+    }
+
+    pub fn try_stack(tensors: &[&Tensor<T>], axis: usize) -> Result<Tensor<T>> {
+        let first = tensors.first().ok_or(TensorError::NoTensors)?;
+        if let Some(t) = tensors.iter().find(|t| t.shape != first.shape) {
+            return Err(TensorError::ShapeMismatch {
+                lhs: first.shape.clone(),
+                rhs: t.shape.clone(),
+            });
+        }
+        let expanded = tensors
+            .iter()
+            .map(|t| t.try_unsqueeze(axis))
+            .collect::<Result<Vec<_>>>()?;
+        Tensor::try_cat(&expanded.iter().collect::<Vec<_>>(), axis)
+    }
+
+    // Views of consecutive blocks along `axis` with the given sizes, which must add up
+    // to the size of the axis
+    #[track_caller]
+    pub fn split(&self, sizes: &[usize], axis: usize) -> Vec<Tensor<T>> {
+        error::unwrap(self.try_split(sizes, axis))
+    }
+
+    pub fn try_split(&self, sizes: &[usize], axis: usize) -> Result<Vec<Tensor<T>>> {
+        self.check_axis(axis)?;
+        if sizes.iter().sum::<usize>() != self.shape[axis] {
+            return Err(TensorError::InvalidSplit {
+                sizes: sizes.to_vec(),
+                size: self.shape[axis],
+            });
+        }
+        let mut start = 0;
+        sizes
+            .iter()
+            .map(|&size| {
+                start += size;
+                self.try_narrow(axis, start - size, size)
+            })
+            .collect()
+    }
+
+    // Splits `axis` into `chunks` blocks of equal size, except for a smaller last one.
+    // Fewer blocks are returned if the axis is too short to fill all of them.
+    #[track_caller]
+    pub fn chunk(&self, chunks: usize, axis: usize) -> Vec<Tensor<T>> {
+        error::unwrap(self.try_chunk(chunks, axis))
+    }
+
+    pub fn try_chunk(&self, chunks: usize, axis: usize) -> Result<Vec<Tensor<T>>> {
+        self.check_axis(axis)?;
+        let size = self.shape[axis];
+        if chunks == 0 {
+            return Err(TensorError::InvalidSplit {
+                sizes: Vec::new(),
+                size,
+            });
+        }
+        let chunk_size = size.div_ceil(chunks);
+        let sizes: Vec<usize> = (0..size)
+            .step_by(chunk_size.max(1))
+            .map(|start| chunk_size.min(size - start))
+            .collect();
+        self.try_split(&sizes, axis)
+    }
+
+    // Repeats every element `repeats` times along `axis`, [a, b] becomes [a, a, b, b]
+    #[track_caller]
+    pub fn repeat(&self, repeats: usize, axis: usize) -> Tensor<T> {
+        error::unwrap(self.try_repeat(repeats, axis))
+    }
+
+    pub fn try_repeat(&self, repeats: usize, axis: usize) -> Result<Tensor<T>> {
+        self.check_axis(axis)?;
+        let mut expanded = self.shape.clone();
+        expanded.insert(axis + 1, repeats);
+        let mut shape = self.shape.clone();
+        shape[axis] *= repeats;
+        self.try_unsqueeze(axis + 1)?
+            .try_broadcast_to(&expanded)?
+            .reshape_exact(&shape)
+    }
+
+    // Repeats the whole tensor `reps[i]` times along axis `i`, [a, b] becomes
+    // [a, b, a, b]. With fewer `reps` than dimensions the leading axes are kept once,
+    // with more the tensor gets new leading axes.
+    #[track_caller]
+    pub fn tile(&self, reps: &[usize]) -> Tensor<T> {
+        error::unwrap(self.try_tile(reps))
+    }
+
+    pub fn try_tile(&self, reps: &[usize]) -> Result<Tensor<T>> {
+        let ndim = self.ndim().max(reps.len());
+        let pad = |dims: &[usize]| [vec![1; ndim - dims.len()], dims.to_vec()].concat();
+        let (dims, reps) = (pad(&self.shape), pad(reps));
+        // Interleave a repeat axis before every axis, [r0, s0, r1, s1, ...]
+        let mut interleaved = Vec::with_capacity(2 * ndim);
+        let mut expanded = Vec::with_capacity(2 * ndim);
+        for (&d, &r) in dims.iter().zip(&reps) {
+            interleaved.extend([1, d]);
+            expanded.extend([r, d]);
+        }
+        let shape: Vec<usize> = dims.iter().zip(&reps).map(|(d, r)| d * r).collect();
+        self.reshape_exact(&interleaved)?
+            .try_broadcast_to(&expanded)?
+            .reshape_exact(&shape)
+    }
+}
@@ -0,0 +1,124 @@
+use super::{broadcast_shape, StridedIter, Tensor};
+use crate::autograd;
+use crate::dtype::Num;
+use crate::error::{self, Result, TensorError};
+use crate::gemm::{self, MatRef};
+
+impl<T: Num> Tensor<T> {
+    // Matrix product with NumPy semantics:
+    // - 1D operands are promoted to a row (left) or column (right) vector and the
+    //   added dimension is removed from the result, so two vectors give a scalar
+    // - dimensions before the last two are batch dimensions and broadcast together
+    #[track_caller]
+    pub fn matmul(&self, other: &Tensor<T>) -> Tensor<T> {
+        error::unwrap(self.try_matmul(other))
+    }
+
+    pub fn try_matmul(&self, other: &Tensor<T>) -> Result<Tensor<T>> {
+        for t in [self, other] {
+            if t.ndim() == 0 {
+                return Err(TensorError::RankMismatch {
+                    expected: 1,
+                    shape: t.shape.clone(),
+                });
+            }
+        }
+        let lhs = if self.ndim() == 1 {
+            self.reshape_exact(&[1, self.shape[0]])?
+        } else {
+            self.clone()
+        };
+        let rhs = if other.ndim() == 1 {
+            other.reshape_exact(&[other.shape[0], 1])?
+        } else {
+            other.clone()
+        };
+
+        // Report mismatches with the shapes the caller passed, not the promoted ones
+        let result = lhs.batched_matmul(&rhs).map_err(|err| match err {
+            TensorError::ShapeMismatch { .. } => TensorError::ShapeMismatch {
+                lhs: self.shape.clone(),
+                rhs: other.shape.clone(),
+            },
+            err => err,
+        })?;
+
+        // Drop the dimensions added by promotion, [..., m, n] with m = 1 and/or n = 1
+        let mut shape = result.shape.clone();
+        if self.ndim() == 1 {
+            shape.remove(shape.len() - 2);
+        }
+        if other.ndim() == 1 {
+            shape.remove(shape.len() - 1);
+        }
+        if shape.len() == result.ndim() {
+            Ok(result)
+        } else {
+            result.reshape_exact(&shape)
+        }
+    }
+
+    // Matrix product of two tensors with at least two dimensions, broadcasting the batch
+    fn batched_matmul(&self, other: &Tensor<T>) -> Result<Tensor<T>> {
+        let (m, k) = (self.shape[self.ndim() - 2], self.shape[self.ndim() - 1]);
+        let (k2, n) = (other.shape[other.ndim() - 2], other.shape[other.ndim() - 1]);
+        if k != k2 {
+            return Err(TensorError::ShapeMismatch {
+                lhs: self.shape.clone(),
+                rhs: other.shape.clone(),
+            });
+        }
+
+        let batch = broadcast_shape(
+            &self.shape[..self.ndim() - 2],
+            &other.shape[..other.ndim() - 2],
+        )
+        .map_err(|err| match err {
+            // Batch dimensions come first in the result, so the axis is unchanged
+            TensorError::NotBroadcastable { axis, .. } => TensorError::NotBroadcastable {
+                lhs: self.shape.clone(),
+                rhs: other.shape.clone(),
+                axis,
+            },
+            err => err,
+        })?;
+        let lhs = self.broadcast_view(&[batch.as_slice(), &[m, k]].concat())?;
+        let rhs = other.broadcast_view(&[batch.as_slice(), &[k, n]].concat())?;
+        let nb = batch.len();
+
+        let batch_size: usize = batch.iter().product();
+        let mut result_data = vec![T::zero(); batch_size * m * n];
+        // Walk both operands through their strides so transposed and broadcast views
+        // need no copy, broadcast batch dimensions have a stride of zero
+        let lhs_batches = StridedIter::new(&batch, &lhs.strides[..nb], lhs.offset);
+        let rhs_batches = StridedIter::new(&batch, &rhs.strides[..nb], rhs.offset);
+        // There are no outputs to walk when a dimension is zero
+        let outputs = result_data.chunks_mut((m * n).max(1));
+        for ((a_offset, b_offset), c) in lhs_batches.zip(rhs_batches).zip(outputs) {
+            let a = MatRef {
+                data: &lhs.storage,
+                offset: a_offset,
+                row_stride: lhs.strides[nb],
+                col_stride: lhs.strides[nb + 1],
+            };
+            let b = MatRef {
+                data: &rhs.storage,
+                offset: b_offset,
+                row_stride: rhs.strides[nb],
+                col_stride: rhs.strides[nb + 1],
+            };
+            gemm::gemm(a, b, c, m, k, n);
+        }
+
+        let result = Tensor::from_data([batch.as_slice(), &[m, n]].concat(), result_data);
+        let (a, b) = (self.detach(), other.detach());
+        // Gradients have the broadcast batch shape and are summed back down to the
+        // shape of each operand
+        Ok(autograd::record(result, &[self, other], move |grad| {
+            vec![
+                grad.matmul(&b.transpose(b.ndim() - 2, b.ndim() - 1)),
+                a.transpose(a.ndim() - 2, a.ndim() - 1).matmul(grad),
+            ]
+        }))
+    }
+}
@@ -0,0 +1,194 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::Tensor;
+use crate::autograd;
+use crate::dtype::Num;
+use crate::error::{self, Result};
+
+impl<T: Num> Tensor<T> {
+    // Compound assignment, which never changes the shape of `self`: `other` has to
+    // broadcast to it. `op` is applied in place when nothing has to be recorded on the
+    // graph (e.g. parameter updates inside `no_grad`). Otherwise `self` is replaced by
+    // the recorded result of `out_of_place`.
+    #[track_caller]
+    fn assign_with<F>(
+        &mut self,
+        other: &Tensor<T>,
+        op: F,
+        out_of_place: fn(&Tensor<T>, &Tensor<T>) -> Result<Tensor<T>>,
+    ) where
+        F: Fn(T, T) -> T,
+    {
+        let rhs = error::unwrap(other.broadcast_view(&self.shape));
+        let tracked = self.node.is_some() || other.node.is_some();
+        if !tracked || !autograd::is_grad_enabled() {
+            for (x, y) in self.data_mut().iter_mut().zip(rhs.iter()) {
+                *x = op(*x, y);
+            }
+            return;
+        }
+        *self = error::unwrap(out_of_place(self, other));
+    }
+}
+
+// Every binary operator for owned and borrowed tensors and scalars on the right. The
+// panicking operators share the errors of the matching `try_*` method.
+macro_rules! impl_binary_op {
+    ($($trait:ident, $method:ident, $try_method:ident, $assign_trait:ident, $assign_method:ident, $op:tt);*) => {
+        $(
+            impl<T: Num> $trait<&Tensor<T>> for &Tensor<T> {
+                type Output = Tensor<T>;
+
+                #[track_caller]
+                fn $method(self, other: &Tensor<T>) -> Tensor<T> {
+                    error::unwrap(self.$try_method(other))
+                }
+            }
+
+            impl<T: Num> $trait<Tensor<T>> for Tensor<T> {
+                type Output = Tensor<T>;
+
+                #[track_caller]
+                fn $method(self, other: Tensor<T>) -> Tensor<T> {
+                    &self $op &other
+                }
+            }
+
+            impl<T: Num> $trait<&Tensor<T>> for Tensor<T> {
+                type Output = Tensor<T>;
+
+                #[track_caller]
+                fn $method(self, other: &Tensor<T>) -> Tensor<T> {
+                    &self $op other
+                }
+            }
+
+            impl<T: Num> $trait<Tensor<T>> for &Tensor<T> {
+                type Output = Tensor<T>;
+
+                #[track_caller]
+                fn $method(self, other: Tensor<T>) -> Tensor<T> {
+                    self $op &other
+                }
+            }
+
+            impl<T: Num> $trait<T> for &Tensor<T> {
+                type Output = Tensor<T>;
+
+                fn $method(self, other: T) -> Tensor<T> {
+                    self $op &Tensor::scalar(other)
+                }
+            }
+
+            impl<T: Num> $trait<T> for Tensor<T> {
+                type Output = Tensor<T>;
+
+                fn $method(self, other: T) -> Tensor<T> {
+                    &self $op &Tensor::scalar(other)
+                }
+            }
+
+            impl<T: Num> $assign_trait<&Tensor<T>> for Tensor<T> {
+                #[track_caller]
+                fn $assign_method(&mut self, other: &Tensor<T>) {
+                    self.assign_with(other, |x, y| x $op y, Tensor::$try_method);
+                }
+            }
+
+            impl<T: Num> $assign_trait<Tensor<T>> for Tensor<T> {
+                #[track_caller]
+                fn $assign_method(&mut self, other: Tensor<T>) {
+                    self.assign_with(&other, |x, y| x $op y, Tensor::$try_method);
+                }
+            }
+
+            impl<T: Num> $assign_trait<T> for Tensor<T> {
+                fn $assign_method(&mut self, other: T) {
+                    self.assign_with(&Tensor::scalar(other), |x, y| x $op y, Tensor::$try_method);
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_op! {
+    Add, add, try_add, AddAssign, add_assign, +;
+    Sub, sub, try_sub, SubAssign, sub_assign, -;
+    Mul, mul, try_mul, MulAssign, mul_assign, *;
+    Div, div, try_div, DivAssign, div_assign, /
+}
+
+// Scalars on the left, e.g. `1.0 - &x`. Implemented per type since a generic
+// `impl<T> Add<Tensor<T>> for T` is not allowed for foreign `T`.
+macro_rules! impl_scalar_lhs {
+    ($($t:ty),*) => {
+        $(
+            impl_scalar_lhs!(@op $t; Add, add, +; Sub, sub, -; Mul, mul, *; Div, div, /);
+        )*
+    };
+    (@op $t:ty; $($trait:ident, $method:ident, $op:tt);*) => {
+        $(
+            impl $trait<&Tensor<$t>> for $t {
+                type Output = Tensor<$t>;
+
+                fn $method(self, other: &Tensor<$t>) -> Tensor<$t> {
+                    &Tensor::scalar(self) $op other
+                }
+            }
+
+            impl $trait<Tensor<$t>> for $t {
+                type Output = Tensor<$t>;
+
+                fn $method(self, other: Tensor<$t>) -> Tensor<$t> {
+                    &Tensor::scalar(self) $op &other
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_lhs!(f32, f64, i32, i64);
+
+impl<T: Num> Neg for &Tensor<T> {
+    type Output = Tensor<T>;
+
+    fn neg(self) -> Tensor<T> {
+        &Tensor::scalar(T::zero()) - self
+    }
+}
+
+impl<T: Num> Neg for Tensor<T> {
+    type Output = Tensor<T>;
+
+    fn neg(self) -> Tensor<T> {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assign_broadcasts_to_the_left_shape() {
+        let mut a = Tensor::ones(vec![2, 3]);
+        a += Tensor::from_data(vec![3], vec![1.0, 2.0, 3.0]);
+        assert_eq!(a.shape(), &[2, 3]);
+        assert_eq!(a.to_vec(), vec![2.0, 3.0, 4.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "do not match")]
+    fn assign_rejects_growing_the_left_operand() {
+        let mut a = Tensor::<f64>::ones(vec![3]);
+        a += Tensor::ones(vec![2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "do not match")]
+    fn tracked_assign_rejects_growing_the_left_operand() {
+        let mut a = Tensor::<f64>::ones(vec![3]);
+        a.set_requires_grad(true);
+        a += Tensor::ones(vec![2, 3]);
+    }
+}
@@ -0,0 +1,356 @@
+use super::{contiguous_strides, Tensor};
+use crate::autograd;
+use crate::dtype::{Element, Float, Num};
+use crate::error::{self, Result, TensorError};
+
+// Only NaN compares unequal to itself
+#[allow(clippy::eq_op)]
+pub(super) fn is_nan<T: Element>(x: T) -> bool {
+    x != x
+}
+
+// Index of the first element of `row` for which `better` holds against every earlier
+// candidate. NaN wins over any number, like in NumPy.
+fn arg_best<T: Element>(row: &[T], better: impl Fn(T, T) -> bool) -> usize {
+    let mut best = 0;
+    for (i, &x) in row.iter().enumerate().skip(1) {
+        if is_nan(row[best]) {
+            break;
+        }
+        if is_nan(x) || better(x, row[best]) {
+            best = i;
+        }
+    }
+    best
+}
+
+impl<T: Element> Tensor<T> {
+    // Elements with `axis` moved last, so that every run of `shape[axis]` consecutive
+    // elements is one row of the reduction
+    pub(super) fn rows(&self, axis: usize) -> Vec<T> {
+        let mut shape = self.shape.clone();
+        let mut strides = self.strides.clone();
+        let size = shape.remove(axis);
+        let stride = strides.remove(axis);
+        shape.push(size);
+        strides.push(stride);
+        self.as_strided(shape, strides, self.offset).to_vec()
+    }
+
+    // Inverse of `rows`, lays out row-ordered `data` as a contiguous tensor of `shape`
+    pub(super) fn from_rows(shape: &[usize], axis: usize, data: Vec<T>) -> Tensor<T> {
+        let mut moved = shape.to_vec();
+        let size = moved.remove(axis);
+        moved.push(size);
+        let mut strides = contiguous_strides(&moved);
+        let stride = strides.pop().unwrap_or(1);
+        strides.insert(axis, stride);
+        Tensor::from_data(moved, data)
+            .as_strided(shape.to_vec(), strides, 0)
+            .contiguous()
+    }
+
+    fn reduced_shape(&self, axis: usize, keepdim: bool) -> Vec<usize> {
+        let mut shape = self.shape.clone();
+        if keepdim {
+            shape[axis] = 1;
+        } else {
+            shape.remove(axis);
+        }
+        shape
+    }
+
+    // Applies `op` to every row along `axis`. The result has size one along `axis`
+    // with `keepdim`, otherwise the axis is removed. Not recorded on the graph.
+    // An axis of size zero gives empty rows, so `op` sees its identity case.
+    fn reduce_rows<U, F>(&self, axis: usize, keepdim: bool, op: F) -> Result<Tensor<U>>
+    where
+        U: Element,
+        F: Fn(&[T]) -> U,
+    {
+        self.check_axis(axis)?;
+        let size = self.shape[axis];
+        let shape = self.reduced_shape(axis, keepdim);
+        let rows = self.rows(axis);
+        let data = (0..shape.iter().product())
+            .map(|i| op(&rows[i * size..(i + 1) * size]))
+            .collect();
+        Tensor::try_from_data(shape, data)
+    }
+
+    // Like `reduce_rows` for reductions without an identity, which reject empty rows
+    fn reduce_nonempty_rows<U, F>(&self, axis: usize, keepdim: bool, op: F) -> Result<Tensor<U>>
+    where
+        U: Element,
+        F: Fn(&[T]) -> U,
+    {
+        self.check_axis(axis)?;
+        if self.shape[axis] == 0 {
+            return Err(TensorError::EmptyReduction { axis });
+        }
+        self.reduce_rows(axis, keepdim, op)
+    }
+
+    // Gradient of a reduction along `axis` broadcast back to the input `shape`
+    fn unreduce(grad: &Tensor<T>, shape: &[usize], axis: usize) -> Tensor<T> {
+        let mut kept = shape.to_vec();
+        kept[axis] = 1;
+        error::unwrap(
+            grad.reshape_exact(&kept)
+                .and_then(|grad| grad.broadcast_view(shape)),
+        )
+    }
+
+    // Single axis holding every element, used by the full-tensor reductions, which
+    // reduce it away to a 0-D result
+    fn flat(&self) -> Tensor<T> {
+        error::unwrap(self.reshape_exact(&[self.numel()]))
+    }
+
+    // Index of the largest element along `axis`, the first one if there are several
+    #[track_caller]
+    pub fn argmax(&self, axis: usize, keepdim: bool) -> Tensor<i64> {
+        error::unwrap(self.try_argmax(axis, keepdim))
+    }
+
+    pub fn try_argmax(&self, axis: usize, keepdim: bool) -> Result<Tensor<i64>> {
+        self.reduce_nonempty_rows(axis, keepdim, |row| arg_best(row, |x, y| x > y) as i64)
+    }
+
+    // Index of the smallest element along `axis`, the first one if there are several
+    #[track_caller]
+    pub fn argmin(&self, axis: usize, keepdim: bool) -> Tensor<i64> {
+        error::unwrap(self.try_argmin(axis, keepdim))
+    }
+
+    pub fn try_argmin(&self, axis: usize, keepdim: bool) -> Result<Tensor<i64>> {
+        self.reduce_nonempty_rows(axis, keepdim, |row| arg_best(row, |x, y| x < y) as i64)
+    }
+
+    // Flat index of the largest element as a 0-D tensor
+    #[track_caller]
+    pub fn argmax_all(&self) -> Tensor<i64> {
+        error::unwrap(self.try_argmax_all())
+    }
+
+    pub fn try_argmax_all(&self) -> Result<Tensor<i64>> {
+        self.flat().try_argmax(0, false)
+    }
+
+    // Flat index of the smallest element as a 0-D tensor
+    #[track_caller]
+    pub fn argmin_all(&self) -> Tensor<i64> {
+        error::unwrap(self.try_argmin_all())
+    }
+
+    pub fn try_argmin_all(&self) -> Result<Tensor<i64>> {
+        self.flat().try_argmin(0, false)
+    }
+}
+
+impl<T: Num> Tensor<T> {
+    // ========================================================================
+    // Reductions
+    // Sum along `axis`, with `keepdim` the result keeps the axis with size one so it
+    // broadcasts against `self`
+    #[track_caller]
+    pub fn sum(&self, axis: usize, keepdim: bool) -> Tensor<T> {
+        error::unwrap(self.try_sum(axis, keepdim))
+    }
+
+    pub fn try_sum(&self, axis: usize, keepdim: bool) -> Result<Tensor<T>> {
+        let result = self.reduce_rows(axis, keepdim, |row| {
+            row.iter().fold(T::zero(), |a, &x| a + x)
+        })?;
+        let shape = self.shape.clone();
+        Ok(autograd::record(result, &[self], move |grad| {
+            vec![Tensor::unreduce(grad, &shape, axis)]
+        }))
+    }
+
+    // Sum of all elements as a 0-D tensor
+    pub fn sum_all(&self) -> Tensor<T> {
+        let result = Tensor::scalar(self.iter().fold(T::zero(), |a, x| a + x));
+        let shape = self.shape.clone();
+        autograd::record(result, &[self], move |grad| {
+            vec![error::unwrap(grad.reshape(&[]).broadcast_view(&shape))]
+        })
+    }
+
+    // Product along `axis`
+    #[track_caller]
+    pub fn prod(&self, axis: usize, keepdim: bool) -> Tensor<T> {
+        error::unwrap(self.try_prod(axis, keepdim))
+    }
+
+    pub fn try_prod(&self, axis: usize, keepdim: bool) -> Result<Tensor<T>> {
+        let result = self.reduce_rows(axis, keepdim, |row| {
+            row.iter().fold(T::one(), |a, &x| a * x)
+        })?;
+        let a = self.detach();
+        Ok(autograd::record(result, &[self], move |grad| {
+            // The derivative for each element is the product of the others in its row,
+            // built from prefix and suffix products so zeros need no special case
+            let size = a.shape[axis];
+            let mut others = Vec::with_capacity(a.numel());
+            // An empty axis leaves no rows and nothing to differentiate
+            for row in a.rows(axis).chunks(size.max(1)) {
+                let start = others.len();
+                let mut acc = T::one();
+                for &x in row {
+                    others.push(acc);
+                    acc = acc * x;
+                }
+                acc = T::one();
+                for (i, &x) in row.iter().enumerate().rev() {
+                    others[start + i] = others[start + i] * acc;
+                    acc = acc * x;
+                }
+            }
+            let others = Tensor::from_rows(&a.shape, axis, others);
+            vec![Tensor::unreduce(grad, &a.shape, axis)
+                .elemwise_with_broadcast(&others, |g, d| g * d)]
+        }))
+    }
+
+    pub fn prod_all(&self) -> Tensor<T> {
+        self.flat().prod(0, false)
+    }
+
+    // Largest element along `axis`, the gradient flows to the first maximum of each row
+    #[track_caller]
+    pub fn max(&self, axis: usize, keepdim: bool) -> Tensor<T> {
+        error::unwrap(self.try_max(axis, keepdim))
+    }
+
+    pub fn try_max(&self, axis: usize, keepdim: bool) -> Result<Tensor<T>> {
+        self.select_along(axis, keepdim, |x, y| x > y)
+    }
+
+    // Smallest element along `axis`, the gradient flows to the first minimum of each row
+    #[track_caller]
+    pub fn min(&self, axis: usize, keepdim: bool) -> Tensor<T> {
+        error::unwrap(self.try_min(axis, keepdim))
+    }
+
+    pub fn try_min(&self, axis: usize, keepdim: bool) -> Result<Tensor<T>> {
+        self.select_along(axis, keepdim, |x, y| x < y)
+    }
+
+    #[track_caller]
+    pub fn max_all(&self) -> Tensor<T> {
+        error::unwrap(self.try_max_all())
+    }
+
+    pub fn try_max_all(&self) -> Result<Tensor<T>> {
+        self.flat().try_max(0, false)
+    }
+
+    #[track_caller]
+    pub fn min_all(&self) -> Tensor<T> {
+        error::unwrap(self.try_min_all())
+    }
+
+    pub fn try_min_all(&self) -> Result<Tensor<T>> {
+        self.flat().try_min(0, false)
+    }
+
+    // Picks one element of every row along `axis` with `arg_best`
+    fn select_along<F>(&self, axis: usize, keepdim: bool, better: F) -> Result<Tensor<T>>
+    where
+        F: Fn(T, T) -> bool + Copy + 'static,
+    {
+        let result = self.reduce_nonempty_rows(axis, keepdim, |row| row[arg_best(row, better)])?;
+        let a = self.detach();
+        Ok(autograd::record(result, &[self], move |grad| {
+            let size = a.shape[axis];
+            let mut mask = vec![T::zero(); a.numel()];
+            for (i, row) in a.rows(axis).chunks(size).enumerate() {
+                mask[i * size + arg_best(row, better)] = T::one();
+            }
+            let mask = Tensor::from_rows(&a.shape, axis, mask);
+            vec![Tensor::unreduce(grad, &a.shape, axis).elemwise_with_broadcast(&mask, |g, m| g * m)]
+        }))
+    }
+}
+
+impl<T: Float> Tensor<T> {
+    // Mean along `axis`
+    #[track_caller]
+    pub fn mean(&self, axis: usize, keepdim: bool) -> Tensor<T> {
+        error::unwrap(self.try_mean(axis, keepdim))
+    }
+
+    pub fn try_mean(&self, axis: usize, keepdim: bool) -> Result<Tensor<T>> {
+        let n = T::from_f64(self.shape.get(axis).copied().unwrap_or(1) as f64);
+        let result = self.reduce_rows(axis, keepdim, |row| {
+            row.iter().fold(T::zero(), |a, &x| a + x) / n
+        })?;
+        let shape = self.shape.clone();
+        Ok(autograd::record(result, &[self], move |grad| {
+            vec![Tensor::unreduce(grad, &shape, axis).map(|g| g / n)]
+        }))
+    }
+
+    // Mean of all elements as a 0-D tensor, NaN for an empty tensor
+    pub fn mean_all(&self) -> Tensor<T> {
+        let n = T::from_f64(self.numel() as f64);
+        let result = Tensor::scalar(self.iter().fold(T::zero(), |a, x| a + x) / n);
+        let shape = self.shape.clone();
+        autograd::record(result, &[self], move |grad| {
+            vec![error::unwrap(
+                grad.map(|g| g / n).reshape(&[]).broadcast_view(&shape),
+            )]
+        })
+    }
+
+    // Variance along `axis`, dividing by `n - ddof` (0 for the population variance,
+    // 1 for the unbiased sample variance)
+    #[track_caller]
+    pub fn var(&self, axis: usize, ddof: usize, keepdim: bool) -> Tensor<T> {
+        error::unwrap(self.try_var(axis, ddof, keepdim))
+    }
+
+    pub fn try_var(&self, axis: usize, ddof: usize, keepdim: bool) -> Result<Tensor<T>> {
+        let size = self.shape.get(axis).copied().unwrap_or(1);
+        let n = T::from_f64(size as f64);
+        let dof = T::from_f64(size as f64 - ddof as f64);
+        let result = self.reduce_rows(axis, keepdim, |row| {
+            let mean = row.iter().fold(T::zero(), |a, &x| a + x) / n;
+            row.iter()
+                .fold(T::zero(), |a, &x| a + (x - mean) * (x - mean))
+                / dof
+        })?;
+        let a = self.detach();
+        Ok(autograd::record(result, &[self], move |grad| {
+            let two = T::one() + T::one();
+            let centered = a.elemwise_with_broadcast(&a.mean(axis, true), |x, m| x - m);
+            vec![Tensor::unreduce(grad, &a.shape, axis)
+                .elemwise_with_broadcast(&centered, |g, c| g * two * c / dof)]
+        }))
+    }
+
+    pub fn var_all(&self, ddof: usize) -> Tensor<T> {
+        self.flat().var(0, ddof, false)
+    }
+
+    // Standard deviation along `axis`, see `var` for `ddof`
+    #[track_caller]
+    pub fn std(&self, axis: usize, ddof: usize, keepdim: bool) -> Tensor<T> {
+        error::unwrap(self.try_std(axis, ddof, keepdim))
+    }
+
+    pub fn try_std(&self, axis: usize, ddof: usize, keepdim: bool) -> Result<Tensor<T>> {
+        let var = self.try_var(axis, ddof, keepdim)?;
+        let result = var.map(|v| v.sqrt());
+        let s = result.detach();
+        Ok(autograd::record(result, &[&var], move |grad| {
+            let two = T::one() + T::one();
+            vec![grad.elemwise_with_broadcast(&s, |g, s| g / (two * s))]
+        }))
+    }
+
+    pub fn std_all(&self, ddof: usize) -> Tensor<T> {
+        self.flat().std(0, ddof, false)
+    }
+}
@@ -0,0 +1,230 @@
+use super::Tensor;
+use crate::autograd;
+use crate::dtype::Float;
+use crate::error::{self, Result};
+
+// sqrt(2 / pi), used by the tanh approximation of GELU
+const SQRT_2_OVER_PI: f64 = 0.797_884_560_802_865_4;
+const GELU_COEFF: f64 = 0.044_715;
+
+// Logistic function, split on the sign of `x` so `exp` never overflows
+fn sigmoid<T: Float>(x: T) -> T {
+    if x >= T::zero() {
+        T::one() / (T::one() + (-x).exp())
+    } else {
+        let e = x.exp();
+        e / (T::one() + e)
+    }
+}
+
+impl<T: Float> Tensor<T> {
+    // Records an element-wise op whose derivative is given by `derivative(x, y)` in
+    // terms of the input `x` and the output `y`
+    fn unary<F, D>(&self, op: F, derivative: D) -> Tensor<T>
+    where
+        F: Fn(T) -> T,
+        D: Fn(T, T) -> T + 'static,
+    {
+        let result = self.map(op);
+        let (x, y) = (self.detach(), result.detach());
+        autograd::record(result, &[self], move |grad| {
+            let d = x.elemwise_with_broadcast(&y, &derivative);
+            vec![grad.elemwise_with_broadcast(&d, |g, d| g * d)]
+        })
+    }
+
+    // ========================================================================
+    // Element-wise math
+    pub fn exp(&self) -> Tensor<T> {
+        self.unary(|x| x.exp(), |_, y| y)
+    }
+
+    // Natural logarithm
+    pub fn ln(&self) -> Tensor<T> {
+        self.unary(|x| x.ln(), |x, _| T::one() / x)
+    }
+
+    pub fn sqrt(&self) -> Tensor<T> {
+        let two = T::from_f64(2.0);
+        self.unary(|x| x.sqrt(), move |_, y| T::one() / (two * y))
+    }
+
+    // Every element raised to the power `exponent`
+    pub fn pow(&self, exponent: T) -> Tensor<T> {
+        self.unary(
+            |x| x.powf(exponent),
+            move |x, _| exponent * x.powf(exponent - T::one()),
+        )
+    }
+
+    // The derivative at zero is taken to be zero
+    pub fn abs(&self) -> Tensor<T> {
+        self.unary(
+            |x| x.abs(),
+            |x, _| {
+                if x > T::zero() {
+                    T::one()
+                } else if x < T::zero() {
+                    -T::one()
+                } else {
+                    T::zero()
+                }
+            },
+        )
+    }
+
+    // Limits every element to `[min, max]`, the gradient only flows through elements
+    // inside the range
+    pub fn clamp(&self, min: T, max: T) -> Tensor<T> {
+        self.unary(
+            move |x| {
+                if x < min {
+                    min
+                } else if x > max {
+                    max
+                } else {
+                    x
+                }
+            },
+            move |x, _| {
+                if x >= min && x <= max {
+                    T::one()
+                } else {
+                    T::zero()
+                }
+            },
+        )
+    }
+
+    pub fn tanh(&self) -> Tensor<T> {
+        self.unary(|x| x.tanh(), |_, y| T::one() - y * y)
+    }
+
+    // ========================================================================
+    // Activations
+    pub fn sigmoid(&self) -> Tensor<T> {
+        self.unary(sigmoid, |_, s| s * (T::one() - s))
+    }
+
+    pub fn relu(&self) -> Tensor<T> {
+        self.leaky_relu(T::zero())
+    }
+
+    // `x` for positive inputs, `negative_slope * x` otherwise
+    pub fn leaky_relu(&self, negative_slope: T) -> Tensor<T> {
+        self.unary(
+            move |x| if x > T::zero() { x } else { negative_slope * x },
+            move |x, _| {
+                if x > T::zero() {
+                    T::one()
+                } else {
+                    negative_slope
+                }
+            },
+        )
+    }
+
+    // `x` for positive inputs, `alpha * (exp(x) - 1)` otherwise
+    pub fn elu(&self, alpha: T) -> Tensor<T> {
+        self.unary(
+            move |x| if x > T::zero() { x } else { alpha * x.exp_m1() },
+            move |x, y| if x > T::zero() { T::one() } else { y + alpha },
+        )
+    }
+
+    // Gaussian error linear unit, using the tanh approximation
+    pub fn gelu(&self) -> Tensor<T> {
+        let (c, k) = (T::from_f64(SQRT_2_OVER_PI), T::from_f64(GELU_COEFF));
+        let half = T::from_f64(0.5);
+        let three = T::from_f64(3.0);
+        self.unary(
+            move |x| half * x * (T::one() + (c * (x + k * x * x * x)).tanh()),
+            move |x, _| {
+                let t = (c * (x + k * x * x * x)).tanh();
+                let dt = (T::one() - t * t) * c * (T::one() + three * k * x * x);
+                half * (T::one() + t) + half * x * dt
+            },
+        )
+    }
+
+    // `x * sigmoid(x)`, also known as swish
+    pub fn silu(&self) -> Tensor<T> {
+        self.unary(
+            |x| x * sigmoid(x),
+            |x, _| {
+                let s = sigmoid(x);
+                s * (T::one() + x * (T::one() - s))
+            },
+        )
+    }
+
+    // Smooth approximation of relu, `ln(1 + exp(x))`
+    pub fn softplus(&self) -> Tensor<T> {
+        self.unary(
+            |x| {
+                let positive = if x > T::zero() { x } else { T::zero() };
+                positive + (-x.abs()).exp().ln_1p()
+            },
+            |x, _| sigmoid(x),
+        )
+    }
+
+    // Exponentials normalized to sum to one along `axis`. The maximum of each row is
+    // subtracted first so large inputs do not overflow.
+    #[track_caller]
+    pub fn softmax(&self, axis: usize) -> Tensor<T> {
+        error::unwrap(self.try_softmax(axis))
+    }
+
+    pub fn try_softmax(&self, axis: usize) -> Result<Tensor<T>> {
+        let result = self.normalize_rows(axis, |row, max, sum| {
+            row.iter().map(|&x| (x - max).exp() / sum).collect()
+        })?;
+        let y = result.detach();
+        Ok(autograd::record(result, &[self], move |grad| {
+            // dx = y * (g - sum(g * y))
+            let gy = grad.elemwise_with_broadcast(&y, |g, y| g * y);
+            let dot = gy.sum(axis, true);
+            let centered = grad.elemwise_with_broadcast(&dot, |g, d| g - d);
+            vec![centered.elemwise_with_broadcast(&y, |c, y| c * y)]
+        }))
+    }
+
+    // Logarithm of `softmax`, computed directly as `x - max - ln(sum(exp(x - max)))`
+    #[track_caller]
+    pub fn log_softmax(&self, axis: usize) -> Tensor<T> {
+        error::unwrap(self.try_log_softmax(axis))
+    }
+
+    pub fn try_log_softmax(&self, axis: usize) -> Result<Tensor<T>> {
+        let result = self.normalize_rows(axis, |row, max, sum| {
+            let log_sum = sum.ln();
+            row.iter().map(|&x| x - max - log_sum).collect()
+        })?;
+        let p = result.map(|y| y.exp());
+        Ok(autograd::record(result, &[self], move |grad| {
+            // dx = g - softmax * sum(g)
+            let total = grad.sum(axis, true);
+            let scaled = p.elemwise_with_broadcast(&total, |p, t| p * t);
+            vec![grad.elemwise_with_broadcast(&scaled, |g, s| g - s)]
+        }))
+    }
+
+    // Maps every row along `axis` with `op(row, max, sum(exp(row - max)))`, not
+    // recorded on the graph
+    fn normalize_rows<F>(&self, axis: usize, op: F) -> Result<Tensor<T>>
+    where
+        F: Fn(&[T], T, T) -> Vec<T>,
+    {
+        self.check_axis(axis)?;
+        let size = self.shape[axis];
+        let mut result_data = Vec::with_capacity(self.numel());
+        // With an empty axis there are no elements and so no rows to visit
+        for row in self.rows(axis).chunks(size.max(1)) {
+            let max = row.iter().fold(row[0], |m, &x| if x > m { x } else { m });
+            let sum = row.iter().fold(T::zero(), |s, &x| s + (x - max).exp());
+            result_data.extend(op(row, max, sum));
+        }
+        Ok(Tensor::from_rows(&self.shape, axis, result_data))
+    }
+}
@@ -0,0 +1,280 @@
+use super::{contiguous_strides, infer_shape, StridedIter, Tensor};
+use crate::autograd;
+use crate::dtype::Element;
+use crate::error::{self, Result, TensorError};
+
+impl<T: Element> Tensor<T> {
+    // ========================================================================
+    // Views
+    // Same elements with a new shape, sharing storage whenever the tensor is contiguous.
+    // One dimension can be given as -1 to be inferred, 0 is a dimension of size zero.
+    #[track_caller]
+    pub fn reshape(&self, shape: &[isize]) -> Tensor<T> {
+        error::unwrap(self.try_reshape(shape))
+    }
+
+    pub fn try_reshape(&self, shape: &[isize]) -> Result<Tensor<T>> {
+        let mut inferred = None;
+        let mut resolved = Vec::with_capacity(shape.len());
+        for (i, &dim) in shape.iter().enumerate() {
+            match dim {
+                -1 => {
+                    if inferred.is_some() {
+                        return Err(TensorError::TooManyInferred {
+                            shape: shape.iter().map(|&d| d.max(0) as usize).collect(),
+                        });
+                    }
+                    inferred = Some(i);
+                    resolved.push(0);
+                }
+                dim if dim < 0 => return Err(TensorError::NegativeDimension { dim }),
+                dim => resolved.push(dim as usize),
+            }
+        }
+        let resolved = infer_shape(resolved, inferred, self.numel())?;
+        self.reshape_exact(&resolved)
+    }
+
+    // Reshape to a fully specified shape
+    pub(crate) fn reshape_exact(&self, shape: &[usize]) -> Result<Tensor<T>> {
+        if shape.iter().product::<usize>() != self.numel() {
+            return Err(TensorError::DataLength {
+                shape: shape.to_vec(),
+                len: self.numel(),
+            });
+        }
+        let source = self.contiguous();
+        let result = source.as_strided(shape.to_vec(), contiguous_strides(shape), source.offset);
+        let original = self.shape.clone();
+        Ok(autograd::record(result, &[self], move |grad| {
+            vec![error::unwrap(grad.reshape_exact(&original))]
+        }))
+    }
+
+    // Swaps two dimensions by swapping their strides
+    #[track_caller]
+    pub fn transpose(&self, dim0: usize, dim1: usize) -> Tensor<T> {
+        error::unwrap(self.try_transpose(dim0, dim1))
+    }
+
+    pub fn try_transpose(&self, dim0: usize, dim1: usize) -> Result<Tensor<T>> {
+        self.check_axis(dim0)?;
+        self.check_axis(dim1)?;
+        let mut axes: Vec<usize> = (0..self.ndim()).collect();
+        axes.swap(dim0, dim1);
+        self.try_permute(&axes)
+    }
+
+    // Transpose of a 2D matrix
+    #[track_caller]
+    pub fn t(&self) -> Tensor<T> {
+        error::unwrap(self.try_t())
+    }
+
+    pub fn try_t(&self) -> Result<Tensor<T>> {
+        if self.ndim() != 2 {
+            return Err(TensorError::RankMismatch {
+                expected: 2,
+                shape: self.shape.clone(),
+            });
+        }
+        self.try_transpose(0, 1)
+    }
+
+    // Reorders the dimensions, dimension `i` of the result is dimension `axes[i]` of `self`
+    #[track_caller]
+    pub fn permute(&self, axes: &[usize]) -> Tensor<T> {
+        error::unwrap(self.try_permute(axes))
+    }
+
+    pub fn try_permute(&self, axes: &[usize]) -> Result<Tensor<T>> {
+        let mut seen = vec![false; self.ndim()];
+        for &axis in axes {
+            if axis >= self.ndim() || std::mem::replace(&mut seen[axis], true) {
+                return Err(TensorError::InvalidPermutation {
+                    axes: axes.to_vec(),
+                    ndim: self.ndim(),
+                });
+            }
+        }
+        if axes.len() != self.ndim() {
+            return Err(TensorError::InvalidPermutation {
+                axes: axes.to_vec(),
+                ndim: self.ndim(),
+            });
+        }
+
+        let shape = axes.iter().map(|&a| self.shape[a]).collect();
+        let strides = axes.iter().map(|&a| self.strides[a]).collect();
+        let result = self.as_strided(shape, strides, self.offset);
+        let mut inverse = vec![0; axes.len()];
+        for (i, &axis) in axes.iter().enumerate() {
+            inverse[axis] = i;
+        }
+        Ok(autograd::record(result, &[self], move |grad| {
+            vec![grad.permute(&inverse)]
+        }))
+    }
+
+    // Removes `axis` if it has size one, otherwise returns the tensor unchanged
+    #[track_caller]
+    pub fn squeeze(&self, axis: usize) -> Tensor<T> {
+        error::unwrap(self.try_squeeze(axis))
+    }
+
+    pub fn try_squeeze(&self, axis: usize) -> Result<Tensor<T>> {
+        self.check_axis(axis)?;
+        let keep: Vec<usize> = (0..self.ndim())
+            .filter(|&i| i != axis || self.shape[i] != 1)
+            .collect();
+        Ok(self.select_dims(&keep))
+    }
+
+    // Removes every dimension of size one
+    pub fn squeeze_all(&self) -> Tensor<T> {
+        let keep: Vec<usize> = (0..self.ndim()).filter(|&i| self.shape[i] != 1).collect();
+        self.select_dims(&keep)
+    }
+
+    // Inserts a dimension of size one before `axis`, `axis == ndim` appends it
+    #[track_caller]
+    pub fn unsqueeze(&self, axis: usize) -> Tensor<T> {
+        error::unwrap(self.try_unsqueeze(axis))
+    }
+
+    pub fn try_unsqueeze(&self, axis: usize) -> Result<Tensor<T>> {
+        if axis > self.ndim() {
+            return Err(TensorError::AxisOutOfRange {
+                axis,
+                ndim: self.ndim() + 1,
+            });
+        }
+        let mut shape = self.shape.clone();
+        let mut strides = self.strides.clone();
+        // The stride of a dimension of size one is never used to step
+        let stride = if axis < self.ndim() {
+            self.strides[axis] * self.shape[axis]
+        } else {
+            1
+        };
+        shape.insert(axis, 1);
+        strides.insert(axis, stride);
+        let result = self.as_strided(shape, strides, self.offset);
+        let original = self.shape.clone();
+        Ok(autograd::record(result, &[self], move |grad| {
+            vec![error::unwrap(grad.reshape_exact(&original))]
+        }))
+    }
+
+    // View keeping only the dimensions in `keep`, every other one must have size one
+    fn select_dims(&self, keep: &[usize]) -> Tensor<T> {
+        let shape = keep.iter().map(|&i| self.shape[i]).collect();
+        let strides = keep.iter().map(|&i| self.strides[i]).collect();
+        let result = self.as_strided(shape, strides, self.offset);
+        let original = self.shape.clone();
+        autograd::record(result, &[self], move |grad| {
+            vec![error::unwrap(grad.reshape_exact(&original))]
+        })
+    }
+
+    // Merges dimensions `start..=end` into one
+    #[track_caller]
+    pub fn flatten(&self, start: usize, end: usize) -> Tensor<T> {
+        error::unwrap(self.try_flatten(start, end))
+    }
+
+    pub fn try_flatten(&self, start: usize, end: usize) -> Result<Tensor<T>> {
+        if self.ndim() == 0 {
+            return self.reshape_exact(&[1]);
+        }
+        self.check_axis(start)?;
+        self.check_axis(end)?;
+        if start > end {
+            return Err(TensorError::AxisOutOfRange {
+                axis: start,
+                ndim: end + 1,
+            });
+        }
+        let mut shape = self.shape[..start].to_vec();
+        shape.push(self.shape[start..=end].iter().product());
+        shape.extend_from_slice(&self.shape[end + 1..]);
+        self.reshape_exact(&shape)
+    }
+
+    // Expands dimensions of size one (and new leading dimensions) to `shape` without copying
+    #[track_caller]
+    pub fn broadcast_to(&self, shape: &[usize]) -> Tensor<T> {
+        error::unwrap(self.try_broadcast_to(shape))
+    }
+
+    pub fn try_broadcast_to(&self, shape: &[usize]) -> Result<Tensor<T>> {
+        let result = self.broadcast_view(shape)?;
+        Ok(autograd::record(result, &[self], move |grad| {
+            // Reduced back to the original shape when accumulated
+            vec![grad.detach()]
+        }))
+    }
+
+    // Like `broadcast_to`, but -1 keeps the size of the matching existing dimension
+    #[track_caller]
+    pub fn expand(&self, shape: &[isize]) -> Tensor<T> {
+        error::unwrap(self.try_expand(shape))
+    }
+
+    pub fn try_expand(&self, shape: &[isize]) -> Result<Tensor<T>> {
+        let lead = shape.len().saturating_sub(self.ndim());
+        let mut resolved = Vec::with_capacity(shape.len());
+        for (i, &dim) in shape.iter().enumerate() {
+            match dim {
+                -1 if i >= lead => resolved.push(self.shape[i - lead]),
+                dim if dim < 0 => return Err(TensorError::NegativeDimension { dim }),
+                dim => resolved.push(dim as usize),
+            }
+        }
+        self.try_broadcast_to(&resolved)
+    }
+
+    // Elements `start..start + length` along `axis`
+    #[track_caller]
+    pub fn narrow(&self, axis: usize, start: usize, length: usize) -> Tensor<T> {
+        error::unwrap(self.try_narrow(axis, start, length))
+    }
+
+    pub fn try_narrow(&self, axis: usize, start: usize, length: usize) -> Result<Tensor<T>> {
+        self.check_axis(axis)?;
+        if start + length > self.shape[axis] {
+            return Err(TensorError::OutOfBounds {
+                axis,
+                index: (start + length) as isize,
+                size: self.shape[axis],
+            });
+        }
+        let mut shape = self.shape.clone();
+        shape[axis] = length;
+        let offset = self.offset + start * self.strides[axis];
+        let result = self.as_strided(shape.clone(), self.strides.clone(), offset);
+
+        let original = self.shape.clone();
+        Ok(autograd::record(result, &[self], move |grad| {
+            // Scatter the gradient back into a zero tensor of the original shape
+            let strides = contiguous_strides(&original);
+            let mut result_data = vec![T::zero(); original.iter().product()];
+            let region = StridedIter::new(&shape, &strides, start * strides[axis]);
+            for (i, g) in region.zip(grad.iter()) {
+                result_data[i] = g;
+            }
+            vec![Tensor::from_data(original.clone(), result_data)]
+        }))
+    }
+
+    pub(crate) fn check_axis(&self, axis: usize) -> Result<()> {
+        if axis < self.ndim() {
+            Ok(())
+        } else {
+            Err(TensorError::AxisOutOfRange {
+                axis,
+                ndim: self.ndim(),
+            })
+        }
+    }
+}